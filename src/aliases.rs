@@ -0,0 +1,157 @@
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use log::error;
+
+use crate::config::{self, UserConfigSchema};
+use crate::{projects, resolve};
+
+#[derive(Args)]
+pub struct Alias {
+    #[command(subcommand)]
+    pub command: AliasCommands,
+}
+
+#[derive(Subcommand)]
+pub enum AliasCommands {
+    /// Add an alias for a project
+    Add(AliasAddArgs),
+    /// Remove an alias
+    Remove(AliasRemoveArgs),
+    /// List all aliases
+    List(AliasListArgs),
+}
+
+#[derive(Args)]
+pub struct AliasAddArgs {
+    /// Alias to use instead of the project name
+    pub alias: String,
+    /// Name of the project
+    pub project: String,
+    /// Replace an alias that points to another project
+    #[clap(short, long)]
+    pub force: bool,
+}
+
+#[derive(Args)]
+pub struct AliasRemoveArgs {
+    /// Alias to remove
+    pub alias: String,
+}
+
+#[derive(Args)]
+pub struct AliasListArgs {}
+
+#[derive(Args)]
+pub struct PinArgs {
+    /// Name or alias of the project
+    pub project: String,
+}
+
+/// The project name an alias points to, or the name itself if it is not an alias
+pub fn resolve_alias(config: &UserConfigSchema, project_name: &str) -> String {
+    config
+        .project_aliases
+        .as_ref()
+        .and_then(|project_aliases| project_aliases.get(project_name))
+        .cloned()
+        .unwrap_or_else(|| project_name.to_string())
+}
+
+pub fn is_pinned(config: &UserConfigSchema, project_name: &str) -> bool {
+    config
+        .pinned_projects
+        .as_ref()
+        .is_some_and(|pinned_projects| pinned_projects.iter().any(|pinned| pinned == project_name))
+}
+
+pub fn add_alias_to_config(alias_add_args: &AliasAddArgs) {
+    let mut config = config::read_config();
+    let (project_name, _) = resolve::resolve_project(&config, &alias_add_args.project);
+
+    if projects::get_project_root(&config, &alias_add_args.alias).is_some() {
+        error!(
+            "{} is already the name of a project and can't be used as an alias",
+            alias_add_args.alias
+        );
+        std::process::exit(1)
+    }
+
+    if let Some(existing_project_name) = config
+        .project_aliases
+        .as_ref()
+        .and_then(|project_aliases| project_aliases.get(&alias_add_args.alias))
+    {
+        if existing_project_name != &project_name && !alias_add_args.force {
+            error!(
+                "Alias {} already points to {}, pass --force to replace it",
+                alias_add_args.alias, existing_project_name
+            );
+            std::process::exit(1)
+        }
+    }
+
+    config
+        .project_aliases
+        .get_or_insert_with(Default::default)
+        .insert(alias_add_args.alias.clone(), project_name);
+
+    config::write_config(config);
+}
+
+pub fn remove_alias_from_config(alias_remove_args: &AliasRemoveArgs) {
+    let mut config = config::read_config();
+    let removed_alias = config
+        .project_aliases
+        .as_mut()
+        .and_then(|project_aliases| project_aliases.remove(&alias_remove_args.alias));
+
+    if removed_alias.is_none() {
+        error!("Alias {} does not exist", alias_remove_args.alias);
+        std::process::exit(1)
+    }
+
+    config::write_config(config);
+}
+
+pub fn list_aliases(config: &UserConfigSchema) {
+    let mut project_aliases: Vec<(&String, &String)> = config
+        .project_aliases
+        .iter()
+        .flat_map(|project_aliases| project_aliases.iter())
+        .collect();
+
+    project_aliases.sort();
+
+    for (alias, project_name) in project_aliases {
+        println!("{} -> {}", alias.bold(), project_name);
+    }
+}
+
+pub fn pin_project(pin_args: &PinArgs) {
+    let mut config = config::read_config();
+    let (project_name, _) = resolve::resolve_project(&config, &pin_args.project);
+    let pinned_projects = config.pinned_projects.get_or_insert_with(Vec::new);
+
+    if !pinned_projects.contains(&project_name) {
+        pinned_projects.push(project_name);
+    }
+
+    config::write_config(config);
+}
+
+pub fn unpin_project(pin_args: &PinArgs) {
+    let mut config = config::read_config();
+    let pinned_projects = config.pinned_projects.clone().unwrap_or_default();
+
+    if pinned_projects.is_empty() {
+        return;
+    }
+
+    let project_name = resolve::resolve_project_name(&config, &pin_args.project, &pinned_projects);
+
+    if let Some(pinned_projects) = &mut config.pinned_projects {
+        pinned_projects.retain(|pinned| pinned != &project_name);
+    }
+
+    config::write_config(config);
+}
@@ -0,0 +1,356 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{Local, TimeZone};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{self, UserConfigSchema};
+use crate::{projects, registry, resolve};
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct ArchiveArgs {
+    #[command(subcommand)]
+    pub command: Option<ArchiveCommands>,
+
+    /// Name or alias of the project to archive. Use `p archive -- list` for a project called list.
+    pub project: Option<String>,
+
+    /// Compress the project to a tar.zst file
+    #[clap(short, long)]
+    pub compress: bool,
+
+    /// Also archive registered projects outside the projects directory
+    #[clap(long)]
+    pub move_files: bool,
+}
+
+#[derive(Subcommand)]
+pub enum ArchiveCommands {
+    /// List archived projects
+    List(ArchiveListArgs),
+}
+
+#[derive(Args)]
+pub struct ArchiveListArgs {}
+
+#[derive(Args)]
+pub struct UnarchiveArgs {
+    /// Name of the archived project
+    pub project: String,
+}
+
+/// Stored next to every archived project, so it can be listed and restored without unpacking it
+#[derive(Deserialize, Serialize)]
+pub struct ArchiveManifestSchema {
+    pub name: String,
+    pub original_path: String,
+    pub archived_at: u64,
+    pub versions: Vec<String>,
+    pub git_remote: Option<String>,
+    pub compressed: bool,
+    /// Whether the project was registered, so it is registered again when it is unarchived
+    #[serde(default)]
+    pub registered: bool,
+}
+
+pub fn get_archive_directory(config: &UserConfigSchema) -> PathBuf {
+    let archive_directory = match &config.archive_dir {
+        Some(archive_dir) => PathBuf::from(shellexpand::tilde(archive_dir).into_owned()),
+        None => config::get_config_directory().join("archive"),
+    };
+
+    if !archive_directory.exists() {
+        std::fs::create_dir_all(&archive_directory).unwrap_or_else(|_| {
+            error!("Unable to create archive directory");
+            std::process::exit(1)
+        });
+    }
+
+    archive_directory
+}
+
+fn get_manifest_path(archive_directory: &Path, project_name: &str) -> PathBuf {
+    archive_directory.join(format!("{}.toml", project_name))
+}
+
+fn get_compressed_archive_path(archive_directory: &Path, project_name: &str) -> PathBuf {
+    archive_directory.join(format!("{}.tar.zst", project_name))
+}
+
+/// Manifests of all archived projects, sorted by name
+pub fn get_archived_projects(config: &UserConfigSchema) -> Vec<ArchiveManifestSchema> {
+    let archive_directory = get_archive_directory(config);
+    let archive_entries = std::fs::read_dir(&archive_directory).unwrap_or_else(|_| {
+        error!("Unable to read archive directory");
+        std::process::exit(1)
+    });
+    let mut archived_projects: Vec<ArchiveManifestSchema> = archive_entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "toml")
+        })
+        .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+        .filter_map(|manifest_content| toml::from_str(&manifest_content).ok())
+        .collect();
+
+    archived_projects.sort_by(|a, b| a.name.cmp(&b.name));
+    archived_projects
+}
+
+/// Move a directory, copying it if it has to cross file systems. Never merges into an existing
+/// directory.
+pub fn move_directory(from: &Path, to: &Path) -> std::io::Result<()> {
+    if to.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists", to.display()),
+        ));
+    }
+
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    match std::fs::rename(from, to) {
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            copy_directory(from, to).inspect_err(|_| {
+                let _ = std::fs::remove_dir_all(to);
+            })?;
+            std::fs::remove_dir_all(from)
+        }
+        result => result,
+    }
+}
+
+fn copy_directory(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let target_path = to.join(entry.file_name());
+
+        if file_type.is_symlink() {
+            std::os::unix::fs::symlink(std::fs::read_link(entry.path())?, &target_path)?;
+        } else if file_type.is_dir() {
+            copy_directory(&entry.path(), &target_path)?;
+        } else {
+            std::fs::copy(entry.path(), &target_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn compress_directory(directory: &Path, archive_path: &Path) -> std::io::Result<()> {
+    let encoder = zstd::Encoder::new(File::create(archive_path)?, 0)?.auto_finish();
+    let mut builder = tar::Builder::new(encoder);
+
+    builder.follow_symlinks(false);
+    builder.append_dir_all(".", directory)?;
+    builder.into_inner()?;
+
+    Ok(())
+}
+
+fn decompress_archive(archive_path: &Path, directory: &Path) -> std::io::Result<()> {
+    let decoder = zstd::Decoder::new(File::open(archive_path)?)?;
+
+    std::fs::create_dir_all(directory)?;
+    tar::Archive::new(decoder).unpack(directory)
+}
+
+pub fn get_git_remote(project_root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(project_root)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+pub fn archive_project(config: &UserConfigSchema, archive_args: &ArchiveArgs) {
+    let project_query = archive_args.project.as_ref().unwrap_or_else(|| {
+        error!("Please specify the project to archive");
+        std::process::exit(1)
+    });
+    let (project_name, project_root) = resolve::resolve_exact_project(config, project_query);
+    let archive_directory = get_archive_directory(config);
+    let manifest_path = get_manifest_path(&archive_directory, &project_name);
+
+    if manifest_path.exists() {
+        error!("A project called {} is already archived", project_name);
+        std::process::exit(1)
+    }
+
+    if !projects::is_in_projects_directory(config, &project_root) && !archive_args.move_files {
+        error!(
+            "{} lives outside the projects directory in {}. Use `p archive --move-files {}` to move it to the archive anyway",
+            project_name,
+            project_root.display(),
+            project_name
+        );
+        std::process::exit(1)
+    }
+
+    let project = projects::get_project_for_directory(project_root.to_str()).unwrap_or_else(|| {
+        error!("Could not find project {}", project_name);
+        std::process::exit(1)
+    });
+    let manifest = ArchiveManifestSchema {
+        name: project_name.clone(),
+        original_path: project_root.to_string_lossy().into_owned(),
+        archived_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        versions: project
+            .versions
+            .iter()
+            .map(|version| version.version.clone())
+            .collect(),
+        git_remote: get_git_remote(&project_root),
+        compressed: archive_args.compress,
+        registered: registry::is_registered(config, &project_name),
+    };
+
+    let manifest_content = toml::to_string(&manifest).unwrap_or_else(|_| {
+        error!("Unable to convert archive manifest to TOML");
+        std::process::exit(1)
+    });
+
+    // The manifest is written first, so an archived project can always be found again
+    std::fs::write(&manifest_path, manifest_content).unwrap_or_else(|_| {
+        error!("Unable to write archive manifest");
+        std::process::exit(1)
+    });
+
+    if archive_args.compress {
+        let archive_path = get_compressed_archive_path(&archive_directory, &project_name);
+
+        compress_directory(&project_root, &archive_path).unwrap_or_else(|e| {
+            let _ = std::fs::remove_file(&archive_path);
+            let _ = std::fs::remove_file(&manifest_path);
+            error!("Unable to compress {}: {}", project_name, e);
+            std::process::exit(1)
+        });
+        std::fs::remove_dir_all(&project_root).unwrap_or_else(|_| {
+            error!("Unable to remove {}", project_root.display());
+            std::process::exit(1)
+        });
+    } else {
+        move_directory(&project_root, &archive_directory.join(&project_name)).unwrap_or_else(|e| {
+            let _ = std::fs::remove_file(&manifest_path);
+            error!("Unable to move {} to the archive: {}", project_name, e);
+            std::process::exit(1)
+        });
+    }
+
+    // The registration would point to a directory that no longer exists
+    if manifest.registered {
+        registry::set_registered_project_root(&project_name, None);
+    }
+
+    println!("Archived {}", project_name);
+}
+
+pub fn list_archived_projects(config: &UserConfigSchema) {
+    for archived_project in get_archived_projects(config) {
+        let archived_at = Local
+            .timestamp_opt(archived_project.archived_at as i64, 0)
+            .single()
+            .map(|archived_at| archived_at.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+
+        println!(
+            "{} ({}) archived {}{}",
+            archived_project.name.bold(),
+            archived_project.versions.join(", "),
+            archived_at,
+            if archived_project.compressed {
+                ", compressed"
+            } else {
+                ""
+            }
+        );
+
+        if let Some(git_remote) = &archived_project.git_remote {
+            println!("  {}", git_remote);
+        }
+    }
+}
+
+pub fn unarchive_project(config: &UserConfigSchema, unarchive_args: &UnarchiveArgs) {
+    let archive_directory = get_archive_directory(config);
+    let archived_project_names: Vec<String> = get_archived_projects(config)
+        .into_iter()
+        .map(|archived_project| archived_project.name)
+        .collect();
+
+    if archived_project_names.is_empty() {
+        error!("No archived project {} found", unarchive_args.project);
+        std::process::exit(1)
+    }
+
+    let project_name =
+        resolve::resolve_project_name(config, &unarchive_args.project, &archived_project_names);
+    let manifest_path = get_manifest_path(&archive_directory, &project_name);
+    let manifest: ArchiveManifestSchema = std::fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|manifest_content| toml::from_str(&manifest_content).ok())
+        .unwrap_or_else(|| {
+            error!("No archived project {} found", project_name);
+            std::process::exit(1)
+        });
+    let project_root = PathBuf::from(&manifest.original_path);
+
+    if project_root.exists() {
+        error!("{} already exists", project_root.display());
+        std::process::exit(1)
+    }
+
+    if manifest.compressed {
+        let archive_path = get_compressed_archive_path(&archive_directory, &manifest.name);
+
+        decompress_archive(&archive_path, &project_root).unwrap_or_else(|e| {
+            error!("Unable to decompress {}: {}", manifest.name, e);
+            std::process::exit(1)
+        });
+        std::fs::remove_file(&archive_path).unwrap_or_else(|_| {
+            error!("Unable to remove {}", archive_path.display());
+            std::process::exit(1)
+        });
+    } else {
+        move_directory(&archive_directory.join(&manifest.name), &project_root).unwrap_or_else(
+            |e| {
+                error!("Unable to restore {}: {}", manifest.name, e);
+                std::process::exit(1)
+            },
+        );
+    }
+
+    if manifest.registered {
+        registry::set_registered_project_root(&manifest.name, Some(&project_root));
+    }
+
+    std::fs::remove_file(&manifest_path).unwrap_or_else(|_| {
+        error!("Unable to remove archive manifest");
+        std::process::exit(1)
+    });
+
+    println!("{}", project_root.display());
+}
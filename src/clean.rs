@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::Args;
+use colored::Colorize;
+use log::error;
+
+use crate::config::UserConfigSchema;
+use crate::projects::{self, Project};
+use crate::{frecency, history};
+
+#[derive(Args)]
+pub struct CleanArgs {
+    /// Only report what would be deleted
+    #[clap(short, long)]
+    pub dry_run: bool,
+
+    /// Only clean projects without activity for this long, e.g. 30d or 2weeks
+    #[clap(long, default_value = "30d")]
+    pub older_than: String,
+
+    /// Only clean projects of this version
+    #[clap(long)]
+    pub version: Option<String>,
+
+    /// Also clean projects with recent activity
+    #[clap(short, long)]
+    pub force: bool,
+}
+
+/// Format a number of bytes with a binary unit, e.g. 1.5 GB
+pub fn format_size(bytes: u64) -> String {
+    let units = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit_index = 0;
+
+    while size >= 1024.0 && unit_index < units.len() - 1 {
+        size /= 1024.0;
+        unit_index += 1;
+    }
+
+    if unit_index == 0 {
+        format!("{} {}", bytes, units[0])
+    } else {
+        format!("{:.1} {}", size, units[unit_index])
+    }
+}
+
+/// Total size of the files in a directory. Symlinks are not followed.
+pub fn get_directory_size(directory: &Path) -> u64 {
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(_) => return 0,
+    };
+
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => get_directory_size(&entry.path()),
+            Ok(file_type) if file_type.is_file() => {
+                entry.metadata().map(|metadata| metadata.len()).unwrap_or(0)
+            }
+            _ => 0,
+        })
+        .sum()
+}
+
+/// The existing artifact directories of all versions of a project. Paths that would leave the
+/// project root and symlinks are skipped.
+pub fn get_artifact_directories(project: &Project) -> Vec<PathBuf> {
+    let mut artifact_directories: Vec<PathBuf> = project
+        .versions
+        .iter()
+        .flat_map(|version| version.artifact_dirs.iter().flatten())
+        .filter(|artifact_dir| {
+            Path::new(artifact_dir)
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        })
+        .map(|artifact_dir| project.root.join(artifact_dir))
+        .filter(|artifact_directory| {
+            std::fs::symlink_metadata(artifact_directory)
+                .is_ok_and(|metadata| metadata.file_type().is_dir())
+        })
+        .collect();
+
+    artifact_directories.sort();
+    artifact_directories.dedup();
+    artifact_directories
+}
+
+fn get_modified_timestamp(path: &Path) -> u64 {
+    std::fs::symlink_metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(|modified| {
+            modified
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs()
+        })
+        .unwrap_or(0)
+}
+
+/// When each project was last visited with p or had a task executed
+fn get_last_uses() -> HashMap<String, u64> {
+    let mut last_uses: HashMap<String, u64> = frecency::read_visits()
+        .into_iter()
+        .map(|(project_name, project_visits)| (project_name, project_visits.last_visit))
+        .collect();
+
+    for record in history::read_history() {
+        let last_use = last_uses.entry(record.project).or_insert(0);
+
+        *last_use = (*last_use).max(record.started_at);
+    }
+
+    last_uses
+}
+
+/// When a project was last used or changed. Ignored files and artifact directories don't count
+/// as changes, since builds write to them.
+fn get_last_activity(
+    project: &Project,
+    artifact_directories: &[PathBuf],
+    last_uses: &HashMap<String, u64>,
+) -> u64 {
+    let last_use = last_uses.get(&project.name).cloned().unwrap_or(0);
+
+    last_use.max(get_last_modified(&project.root, artifact_directories))
+}
+
+/// When a file in the project was last changed, leaving out .git and the build artifacts
+pub fn get_last_modified(project_root: &Path, artifact_directories: &[PathBuf]) -> u64 {
+    let artifact_directories = artifact_directories.to_vec();
+
+    ignore::WalkBuilder::new(project_root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            entry.file_name() != ".git"
+                && !artifact_directories.contains(&entry.path().to_path_buf())
+        })
+        .build()
+        .flatten()
+        .map(|entry| get_modified_timestamp(entry.path()))
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn clean_projects(config: &UserConfigSchema, clean_args: &CleanArgs) {
+    let older_than: Duration =
+        humantime::parse_duration(&clean_args.older_than).unwrap_or_else(|e| {
+            error!("Invalid duration {}: {}", clean_args.older_than, e);
+            std::process::exit(1)
+        });
+    let active_since = SystemTime::now()
+        .checked_sub(older_than)
+        .unwrap_or(UNIX_EPOCH)
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let last_uses = get_last_uses();
+    let mut reclaimable_size = 0;
+
+    for (_, project_root) in projects::get_project_roots(config) {
+        let project = match projects::get_project_or_skip(&project_root) {
+            Some(project) => project,
+            None => continue,
+        };
+
+        if let Some(version_name) = &clean_args.version {
+            if !project
+                .versions
+                .iter()
+                .any(|version| version.version.eq_ignore_ascii_case(version_name))
+            {
+                continue;
+            }
+        }
+
+        let artifact_directories = get_artifact_directories(&project);
+
+        if artifact_directories.is_empty() {
+            continue;
+        }
+
+        if !clean_args.force
+            && get_last_activity(&project, &artifact_directories, &last_uses) > active_since
+        {
+            println!(
+                "{} skipped, active in the last {}",
+                project.name.bold(),
+                clean_args.older_than
+            );
+            continue;
+        }
+
+        for artifact_directory in artifact_directories {
+            let artifact_size = get_directory_size(&artifact_directory);
+            let relative_artifact_directory = artifact_directory
+                .strip_prefix(&project.root)
+                .unwrap_or(&artifact_directory)
+                .display()
+                .to_string();
+
+            if clean_args.dry_run {
+                reclaimable_size += artifact_size;
+                println!(
+                    "{} {} ({})",
+                    project.name.bold(),
+                    relative_artifact_directory,
+                    format_size(artifact_size)
+                );
+                continue;
+            }
+
+            if let Err(e) = std::fs::remove_dir_all(&artifact_directory) {
+                error!("Unable to remove {}: {}", artifact_directory.display(), e);
+                continue;
+            }
+
+            reclaimable_size += artifact_size;
+
+            println!(
+                "{} removed {} ({})",
+                project.name.bold(),
+                relative_artifact_directory,
+                format_size(artifact_size)
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "{} {}",
+        if clean_args.dry_run {
+            "Reclaimable:"
+        } else {
+            "Reclaimed:"
+        },
+        format_size(reclaimable_size)
+    );
+}
@@ -0,0 +1,199 @@
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use clap::{Args, ValueEnum};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{self, UserConfigSchema};
+use crate::{projects, tasks};
+
+/// Name of the version task that runs after a project has been cloned
+pub const POST_CLONE_TASK: &str = "post_clone";
+
+#[derive(Args)]
+pub struct CloneArgs {
+    /// URL of the git repository
+    pub url: String,
+
+    /// Name of the project, defaults to the name of the repository
+    pub name: Option<String>,
+
+    /// Directory layout in the projects directory, overrides clone_layout in the config
+    #[clap(short, long)]
+    pub layout: Option<CloneLayout>,
+
+    /// Do not run the post_clone task of the detected version
+    #[clap(long)]
+    pub no_post_clone: bool,
+}
+
+#[derive(ValueEnum, Deserialize, Serialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CloneLayout {
+    /// ~/Projects/repo
+    Flat,
+    /// ~/Projects/host/owner/repo
+    Nested,
+}
+
+/// Split a git URL into its host and path, e.g. `git@github.com:owner/repo.git` into
+/// `github.com` and `["owner", "repo"]`. Local paths have no host.
+fn parse_repository_url(url: &str) -> (Option<String>, Vec<String>) {
+    let url = url.trim_end_matches('/').trim_end_matches(".git");
+    let (host, path) = if let Some((_, address)) = url.split_once("://") {
+        match address.split_once('/') {
+            Some((host, path)) => (Some(host), path),
+            None => (None, address),
+        }
+    } else {
+        match url.split_once(':') {
+            Some((host, path)) if !host.contains('/') => (Some(host), path),
+            _ => (None, url),
+        }
+    };
+    let host = host
+        .map(|host| {
+            let host = host.rsplit('@').next().unwrap_or(host);
+
+            host.split(':').next().unwrap_or(host).to_string()
+        })
+        .filter(|host| !host.is_empty());
+    let path = path
+        .split('/')
+        .filter(|component| !component.is_empty() && *component != "." && *component != "..")
+        .map(|component| component.to_string())
+        .collect();
+
+    (host, path)
+}
+
+/// The directory a repository is cloned to, relative to the projects directory
+fn get_clone_directory(url: &str, name: &Option<String>, layout: CloneLayout) -> PathBuf {
+    let (host, mut path) = parse_repository_url(url);
+
+    if path.is_empty() {
+        error!("Unable to derive a project name from {}", url);
+        std::process::exit(1)
+    }
+
+    if let Some(name) = name {
+        path.pop();
+        path.push(name.clone());
+    }
+
+    // Also covers names derived from the URL, like a repository called .github
+    let project_name = path.last().unwrap();
+
+    if !projects::is_valid_project_name(project_name) {
+        error!("{} is not a valid project name", project_name);
+        std::process::exit(1)
+    }
+
+    match (layout, host) {
+        (CloneLayout::Flat, _) => PathBuf::from(path.last().unwrap()),
+        (CloneLayout::Nested, Some(host)) if projects::is_valid_project_name(&host) => {
+            let mut clone_directory = PathBuf::from(host);
+
+            clone_directory.extend(path);
+            clone_directory
+        }
+        (CloneLayout::Nested, _) => {
+            error!(
+                "Unable to derive a host from {}. Use --layout flat instead.",
+                url
+            );
+            std::process::exit(1)
+        }
+    }
+}
+
+pub fn clone_project(config: &UserConfigSchema, clone_args: &CloneArgs) {
+    let layout = clone_args
+        .layout
+        .or(config.clone_layout)
+        .unwrap_or(CloneLayout::Flat);
+    let clone_directory = get_clone_directory(&clone_args.url, &clone_args.name, layout);
+    let project_name = clone_directory
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .into_owned();
+    let project_root =
+        PathBuf::from(shellexpand::tilde(&config.projects_dir).into_owned()).join(&clone_directory);
+
+    if project_root.exists() {
+        error!("{} already exists", project_root.display());
+        std::process::exit(1)
+    }
+
+    // Nested clones are registered so they can be found by the name of the repository
+    if layout == CloneLayout::Nested && projects::get_project_root(config, &project_name).is_some()
+    {
+        error!(
+            "A project called {} already exists. Pass a NAME to choose another name.",
+            project_name
+        );
+        std::process::exit(1)
+    }
+
+    // Keep stdout free for the path of the project, so `cd $(p clone URL)` works
+    let clone_status = Command::new("git")
+        .arg("clone")
+        .arg(&clone_args.url)
+        .arg(&project_root)
+        .stdout(Stdio::from(std::io::stderr()))
+        .status()
+        .unwrap_or_else(|_| {
+            error!("Unable to run git");
+            std::process::exit(1)
+        });
+
+    if !clone_status.success() {
+        error!("Unable to clone {}", clone_args.url);
+        std::process::exit(clone_status.code().unwrap_or(1))
+    }
+
+    if layout == CloneLayout::Nested {
+        let mut config = config::read_config();
+
+        config
+            .registered_projects
+            .get_or_insert_with(Default::default)
+            .insert(
+                project_name.clone(),
+                project_root.to_string_lossy().into_owned(),
+            );
+        config::write_config(config);
+    }
+
+    let project = projects::get_project_for_directory(project_root.to_str()).unwrap_or_else(|| {
+        error!("Could not find project {}", project_name);
+        std::process::exit(1)
+    });
+    let project_version = &project.versions[0];
+
+    eprintln!("Detected version {}", project_version.version);
+
+    // Only the version's task runs, a freshly cloned .p.toml is not trusted to run commands
+    let version_tasks = project_version.tasks.clone().unwrap_or_default();
+
+    let exit_code = if !clone_args.no_post_clone && version_tasks.contains_key(POST_CLONE_TASK) {
+        tasks::run_task(
+            &project.root,
+            &version_tasks,
+            POST_CLONE_TASK,
+            &[],
+            &[],
+            true,
+        )
+    } else {
+        0
+    };
+
+    println!("{}", project.root.display());
+
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+}
@@ -1,13 +1,24 @@
 use log::error;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::clone::CloneLayout;
+
 #[derive(Deserialize, Serialize)]
 pub struct UserConfigSchema {
     pub projects_dir: String,
     pub project_management_tool: String,
     pub version_repositories: Option<Vec<String>>,
     pub editor: Option<String>,
+    pub project_editors: Option<HashMap<String, String>>,
+    pub project_aliases: Option<HashMap<String, String>>,
+    pub pinned_projects: Option<Vec<String>>,
+    pub registered_projects: Option<HashMap<String, String>>,
+    pub clone_layout: Option<CloneLayout>,
+    pub archive_dir: Option<String>,
+    pub trash_retention_days: Option<u64>,
+    pub discover_tool_tasks: Option<bool>,
 }
 
 pub fn read_config() -> UserConfigSchema {
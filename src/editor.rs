@@ -0,0 +1,183 @@
+use std::path::Path;
+
+use log::error;
+
+use crate::config::UserConfigSchema;
+use crate::project_config::PROJECT_CONFIG_FILE;
+use crate::projects::Project;
+use crate::{frecency, projects, resolve, shell, EditArgs};
+
+const EDITOR_PLACEHOLDERS: [&str; 3] = ["{root}", "{file}", "{line}"];
+
+/// Fill the {root}, {file} and {line} placeholders of an editor command. If `append_target` is
+/// set, editors without placeholders get the file or the root appended, so plain commands like
+/// `code` open the project.
+pub fn render_editor_command(
+    editor: &str,
+    root: &Path,
+    file: &Option<String>,
+    line: &Option<usize>,
+    append_target: bool,
+) -> String {
+    let root_string = shell::quote_argument(&root.to_string_lossy());
+    let file_string = shell::quote_argument(file.as_deref().unwrap_or("."));
+    let line_string = line.unwrap_or(1).to_string();
+
+    if !EDITOR_PLACEHOLDERS
+        .iter()
+        .any(|placeholder| editor.contains(placeholder))
+    {
+        if !append_target {
+            return editor.to_string();
+        }
+
+        return match file {
+            Some(file) => format!(
+                "{} {}",
+                editor,
+                shell::quote_argument(&root.join(file).to_string_lossy())
+            ),
+            None => format!("{} {}", editor, root_string),
+        };
+    }
+
+    editor
+        .replace("{root}", &root_string)
+        .replace("{file}", &file_string)
+        .replace("{line}", &line_string)
+}
+
+/// Find the project for a (partial) project name
+fn get_project_by_query(config: &UserConfigSchema, project_query: &str) -> Project {
+    let (project_name, project_root) = resolve::resolve_project(config, project_query);
+
+    projects::get_project_for_directory(project_root.to_str()).unwrap_or_else(|| {
+        error!("Could not find project {}", project_name);
+        std::process::exit(1)
+    })
+}
+
+/// Pick the editor for a project and describe where it was configured. The --editor flag wins,
+/// followed by the project, the version, the user config and finally $VISUAL and $EDITOR.
+pub fn resolve_editor(
+    config: &UserConfigSchema,
+    editor_flag: &Option<String>,
+    project: Option<&Project>,
+) -> Option<(String, String)> {
+    if let Some(editor) = editor_flag {
+        return Some((editor.clone(), "--editor flag".to_string()));
+    }
+
+    if let Some(project) = project {
+        if let Some(editor) = config
+            .project_editors
+            .as_ref()
+            .and_then(|project_editors| project_editors.get(&project.name))
+        {
+            return Some((editor.clone(), "project_editors in config".to_string()));
+        }
+
+        if let Some(editor) = project
+            .config
+            .as_ref()
+            .and_then(|project_config| project_config.editor.as_ref())
+        {
+            return Some((editor.clone(), PROJECT_CONFIG_FILE.to_string()));
+        }
+
+        if let Some(version) = project
+            .versions
+            .iter()
+            .find(|version| version.editor.is_some())
+        {
+            return Some((
+                version.editor.clone().unwrap(),
+                format!("version {}", version.version),
+            ));
+        }
+    }
+
+    if let Some(editor) = &config.editor {
+        return Some((editor.clone(), "config".to_string()));
+    }
+
+    for variable in ["VISUAL", "EDITOR"] {
+        if let Ok(editor) = std::env::var(variable) {
+            if !editor.is_empty() {
+                return Some((editor, format!("${}", variable)));
+            }
+        }
+    }
+
+    None
+}
+
+pub fn open_editor_in_current_project(config: &UserConfigSchema, edit_args: &EditArgs) {
+    let current_directory = std::env::current_dir().unwrap_or_else(|_| {
+        error!("Unable to get current directory");
+        std::process::exit(1)
+    });
+
+    // With --here or outside of a project, the editor runs as is in the current directory
+    let project = if let Some(project_query) = &edit_args.project {
+        let project = get_project_by_query(config, project_query);
+
+        frecency::record_visit(&project.name);
+        Some(project)
+    } else if edit_args.here {
+        None
+    } else {
+        projects::get_project_for_directory(None)
+    };
+    let editor = match resolve_editor(config, &edit_args.editor, project.as_ref()) {
+        Some((editor, _)) => editor,
+        None => {
+            println!(
+                "No editor set. Please set your preferred code editor or IDE in your config file. Or specify an editor with the --editor flag."
+            );
+
+            return;
+        }
+    };
+    let (editor_directory, append_target) = match project {
+        Some(project) => (project.root, true),
+        None => (current_directory, edit_args.file.is_some()),
+    };
+    let editor_command = render_editor_command(
+        &editor,
+        &editor_directory,
+        &edit_args.file,
+        &edit_args.line,
+        append_target,
+    );
+
+    let mut command = std::process::Command::new("sh");
+
+    command
+        .arg("-c")
+        .arg(editor_command)
+        .current_dir(editor_directory);
+
+    // If the detach flag is set, run the editor in the background
+    if edit_args.detach {
+        command.spawn().unwrap_or_else(|_| {
+            error!("Failed to run editor");
+            std::process::exit(1)
+        });
+
+        return;
+    }
+
+    // Otherwise, run the editor in the foreground
+    command
+        .spawn()
+        .unwrap_or_else(|_| {
+            error!("Failed to run editor");
+            std::process::exit(1)
+        })
+        .wait()
+        .unwrap_or_else(|_| {
+            error!("Editor returned a non-zero status");
+            std::process::exit(1)
+        });
+}
@@ -0,0 +1,152 @@
+use std::path::Path;
+
+use colored::Colorize;
+use log::error;
+
+use crate::project_config::PROJECT_CONFIG_FILE;
+use crate::projects::Project;
+use crate::ExecuteArgs;
+
+pub struct EnvironmentVariable {
+    pub key: String,
+    pub value: String,
+    pub origin: String,
+}
+
+pub fn get_default_env_files() -> Vec<String> {
+    vec![".env".to_string(), ".env.local".to_string()]
+}
+
+pub fn get_project_environment(
+    project: &Project,
+    execute_args: &ExecuteArgs,
+) -> Vec<EnvironmentVariable> {
+    let project_root = &project.root;
+    let project_version = &project.versions[0];
+    let mut environment: Vec<EnvironmentVariable> = vec![];
+
+    if execute_args.no_env {
+        return environment;
+    }
+
+    // Files passed with --env-file replace the ones configured by the version and must exist
+    let (env_files, env_files_required) = if !execute_args.env_file.is_empty() {
+        (execute_args.env_file.clone(), true)
+    } else {
+        match &project_version.env_files {
+            Some(env_files) => (env_files.clone(), false),
+            None => (get_default_env_files(), false),
+        }
+    };
+
+    for env_file in env_files {
+        let env_file_path = project_root.join(shellexpand::tilde(&env_file).into_owned());
+
+        if !env_file_path.exists() {
+            if env_files_required {
+                error!("Environment file {} does not exist", env_file);
+                std::process::exit(1);
+            }
+
+            continue;
+        }
+
+        for (key, value) in read_env_file(&env_file_path) {
+            set_environment_variable(&mut environment, key, value, &env_file);
+        }
+    }
+
+    // Variables set in the project config override the env files
+    if let Some(project_config_env) = project
+        .config
+        .as_ref()
+        .and_then(|project_config| project_config.env.as_ref())
+    {
+        for (key, value) in project_config_env {
+            set_environment_variable(
+                &mut environment,
+                key.clone(),
+                value.clone(),
+                PROJECT_CONFIG_FILE,
+            );
+        }
+    }
+
+    environment
+}
+
+/// Set a variable, replacing one with the same key that was set earlier
+fn set_environment_variable(
+    environment: &mut Vec<EnvironmentVariable>,
+    key: String,
+    value: String,
+    origin: &str,
+) {
+    environment.retain(|variable| variable.key != key);
+    environment.push(EnvironmentVariable {
+        key,
+        value,
+        origin: origin.to_string(),
+    });
+}
+
+pub fn read_env_file(env_file_path: &Path) -> Vec<(String, String)> {
+    let env_file_entries = dotenvy::from_path_iter(env_file_path).unwrap_or_else(|_| {
+        error!(
+            "Unable to read environment file {}",
+            env_file_path.display()
+        );
+        std::process::exit(1)
+    });
+
+    env_file_entries
+        .map(|entry| {
+            entry.unwrap_or_else(|e| {
+                error!(
+                    "Invalid environment file {}: {}",
+                    env_file_path.display(),
+                    e
+                );
+                std::process::exit(1)
+            })
+        })
+        .collect()
+}
+
+pub fn explain_environment(environment: &[EnvironmentVariable]) {
+    if environment.is_empty() {
+        println!("No environment variables are loaded for this project");
+        return;
+    }
+
+    println!(
+        "{}",
+        format!(
+            "{} Environment {}:",
+            environment.len(),
+            if environment.len() == 1 {
+                "variable"
+            } else {
+                "variables"
+            }
+        )
+        .bold()
+        .underline()
+    );
+    println!();
+
+    for variable in environment {
+        let shadowed_string = if std::env::var(&variable.key).is_ok() {
+            " (overrides the shell environment)"
+        } else {
+            ""
+        };
+
+        println!(
+            "{} from {}{}",
+            variable.key.bold(),
+            variable.origin,
+            shadowed_string
+        );
+    }
+}
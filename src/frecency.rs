@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+
+#[derive(Deserialize, Serialize)]
+pub struct ProjectVisits {
+    pub count: u64,
+    pub last_visit: u64,
+}
+
+pub fn get_visits_path() -> PathBuf {
+    let mut visits_path = config::get_config_directory();
+    visits_path.push("visits.json");
+
+    visits_path
+}
+
+pub fn read_visits() -> HashMap<String, ProjectVisits> {
+    let visits_path = get_visits_path();
+
+    if !visits_path.exists() {
+        return HashMap::new();
+    }
+
+    let visits_content = std::fs::read_to_string(visits_path).unwrap_or_else(|_| {
+        error!("Unable to read visits file");
+        std::process::exit(1)
+    });
+
+    serde_json::from_str(&visits_content).unwrap_or_default()
+}
+
+pub fn write_visits(visits: &HashMap<String, ProjectVisits>) {
+    let visits_content = serde_json::to_string(visits).unwrap_or_else(|_| {
+        error!("Unable to convert visits to JSON");
+        std::process::exit(1)
+    });
+
+    std::fs::write(get_visits_path(), visits_content).unwrap_or_else(|_| {
+        error!("Unable to write visits file");
+        std::process::exit(1)
+    });
+}
+
+pub fn record_visit(project_name: &str) {
+    let mut visits = read_visits();
+    let project_visits = visits
+        .entry(project_name.to_string())
+        .or_insert(ProjectVisits {
+            count: 0,
+            last_visit: 0,
+        });
+
+    project_visits.count += 1;
+    project_visits.last_visit = get_current_timestamp();
+
+    write_visits(&visits);
+}
+
+/// Move the visits of a project to its new name
+pub fn rename_project_visits(old_project_name: &str, new_project_name: &str) {
+    let mut visits = read_visits();
+
+    if let Some(project_visits) = visits.remove(old_project_name) {
+        visits.insert(new_project_name.to_string(), project_visits);
+        write_visits(&visits);
+    }
+}
+
+/// Forget the visits of a project and return them
+pub fn remove_project_visits(project_name: &str) -> Option<ProjectVisits> {
+    let mut visits = read_visits();
+    let project_visits = visits.remove(project_name);
+
+    if project_visits.is_some() {
+        write_visits(&visits);
+    }
+
+    project_visits
+}
+
+pub fn restore_project_visits(project_name: &str, project_visits: ProjectVisits) {
+    let mut visits = read_visits();
+
+    visits.insert(project_name.to_string(), project_visits);
+    write_visits(&visits);
+}
+
+/// Score projects by how often and how recently they were visited
+pub fn get_frecency_scores() -> HashMap<String, f64> {
+    let current_timestamp = get_current_timestamp();
+
+    read_visits()
+        .into_iter()
+        .map(|(project_name, project_visits)| {
+            let seconds_since_visit = current_timestamp.saturating_sub(project_visits.last_visit);
+            let recency_weight = match seconds_since_visit {
+                0..=3_600 => 4.0,
+                3_601..=86_400 => 2.0,
+                86_401..=604_800 => 0.5,
+                _ => 0.25,
+            };
+
+            (project_name, project_visits.count as f64 * recency_weight)
+        })
+        .collect()
+}
+
+fn get_current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
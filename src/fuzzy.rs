@@ -0,0 +1,394 @@
+use clap::ValueEnum;
+use colored::Colorize;
+
+const SCORE_MATCH: i64 = 16;
+const SCORE_GAP_START: i64 = -3;
+const SCORE_GAP_EXTENSION: i64 = -1;
+const BONUS_BOUNDARY: i64 = 8;
+/// Words separated by a delimiter like - or / are more likely meant than whitespace separated ones
+const BONUS_BOUNDARY_DELIMITER: i64 = 9;
+const BONUS_CAMEL_CASE: i64 = 7;
+const BONUS_CONSECUTIVE: i64 = 4;
+const BONUS_FIRST_CHARACTER_MULTIPLIER: i64 = 2;
+const NO_MATCH: i64 = i64::MIN / 2;
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum CaseSensitivity {
+    /// Case sensitive only if the query contains an uppercase letter
+    #[default]
+    Smart,
+    Sensitive,
+    Insensitive,
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum MatchOn {
+    /// Match on the project name
+    #[default]
+    Name,
+    /// Match on the full path of the project
+    Path,
+}
+
+pub struct FuzzyMatch {
+    pub score: i64,
+    /// Indices of the matched characters in the candidate
+    pub positions: Vec<usize>,
+}
+
+fn is_case_sensitive(query: &str, case_sensitivity: CaseSensitivity) -> bool {
+    match case_sensitivity {
+        CaseSensitivity::Smart => query.chars().any(|character| character.is_uppercase()),
+        CaseSensitivity::Sensitive => true,
+        CaseSensitivity::Insensitive => false,
+    }
+}
+
+fn normalize(text: &str, case_sensitive: bool) -> Vec<char> {
+    if case_sensitive {
+        text.chars().collect()
+    } else {
+        // Lowercase character by character so indices keep pointing at the original text
+        text.chars()
+            .map(|character| character.to_lowercase().next().unwrap_or(character))
+            .collect()
+    }
+}
+
+fn get_bonus(previous_character: Option<char>, character: char) -> i64 {
+    match previous_character {
+        None => BONUS_BOUNDARY,
+        Some(previous_character)
+            if "-_./\\:".contains(previous_character) && character.is_alphanumeric() =>
+        {
+            BONUS_BOUNDARY_DELIMITER
+        }
+        Some(previous_character)
+            if !previous_character.is_alphanumeric() && character.is_alphanumeric() =>
+        {
+            BONUS_BOUNDARY
+        }
+        Some(previous_character)
+            if previous_character.is_lowercase() && character.is_uppercase() =>
+        {
+            BONUS_CAMEL_CASE
+        }
+        Some(previous_character)
+            if !previous_character.is_ascii_digit() && character.is_ascii_digit() =>
+        {
+            BONUS_CAMEL_CASE
+        }
+        _ => 0,
+    }
+}
+
+/// Match the query as a subsequence of the candidate. Matches at the start of words and runs of
+/// consecutive characters score higher, gaps between matched characters lower, so "cpf" finds
+/// "customer-portal-frontend".
+pub fn fuzzy_match(
+    query: &str,
+    candidate: &str,
+    case_sensitivity: CaseSensitivity,
+) -> Option<FuzzyMatch> {
+    let case_sensitive = is_case_sensitive(query, case_sensitivity);
+    let query: Vec<char> = normalize(query, case_sensitive)
+        .into_iter()
+        .filter(|character| !character.is_whitespace())
+        .collect();
+    let original_candidate: Vec<char> = candidate.chars().collect();
+    let candidate = normalize(candidate, case_sensitive);
+
+    if query.is_empty() {
+        return None;
+    }
+
+    let mut remaining_query = query.iter().peekable();
+
+    for character in &candidate {
+        if remaining_query.peek() == Some(&character) {
+            remaining_query.next();
+        }
+    }
+
+    if remaining_query.peek().is_some() {
+        return None;
+    }
+
+    let bonuses: Vec<i64> = original_candidate
+        .iter()
+        .enumerate()
+        .map(|(index, character)| {
+            get_bonus(
+                index.checked_sub(1).map(|index| original_candidate[index]),
+                *character,
+            )
+        })
+        .collect();
+    let mut scores = vec![vec![NO_MATCH; candidate.len()]; query.len()];
+    let mut previous_positions = vec![vec![0; candidate.len()]; query.len()];
+
+    for (query_index, query_character) in query.iter().enumerate() {
+        // Best score to continue from after a gap, together with where it came from
+        let mut best_gap: Option<(i64, usize)> = None;
+
+        for (candidate_index, candidate_character) in candidate.iter().enumerate() {
+            if query_index > 0 && candidate_index >= 2 {
+                let gap_start_index = candidate_index - 2;
+                let gap_start_score = scores[query_index - 1][gap_start_index];
+
+                best_gap = best_gap.map(|(score, index)| (score + SCORE_GAP_EXTENSION, index));
+
+                if gap_start_score > NO_MATCH
+                    && best_gap.is_none_or(|(score, _)| gap_start_score + SCORE_GAP_START > score)
+                {
+                    best_gap = Some((gap_start_score + SCORE_GAP_START, gap_start_index));
+                }
+            }
+
+            if candidate_character != query_character {
+                continue;
+            }
+
+            if query_index == 0 {
+                scores[0][candidate_index] =
+                    SCORE_MATCH + bonuses[candidate_index] * BONUS_FIRST_CHARACTER_MULTIPLIER;
+                continue;
+            }
+
+            if candidate_index > 0 && scores[query_index - 1][candidate_index - 1] > NO_MATCH {
+                scores[query_index][candidate_index] = scores[query_index - 1][candidate_index - 1]
+                    + SCORE_MATCH
+                    + bonuses[candidate_index].max(BONUS_CONSECUTIVE);
+                previous_positions[query_index][candidate_index] = candidate_index - 1;
+            }
+
+            if let Some((gap_score, gap_index)) = best_gap {
+                let score = gap_score + SCORE_MATCH + bonuses[candidate_index];
+
+                if score > scores[query_index][candidate_index] {
+                    scores[query_index][candidate_index] = score;
+                    previous_positions[query_index][candidate_index] = gap_index;
+                }
+            }
+        }
+    }
+
+    let last_query_index = query.len() - 1;
+    let (mut position, score) = scores[last_query_index]
+        .iter()
+        .cloned()
+        .enumerate()
+        .max_by_key(|(_, score)| *score)?;
+
+    if score <= NO_MATCH {
+        return None;
+    }
+
+    let mut positions = vec![position];
+
+    for query_index in (1..=last_query_index).rev() {
+        position = previous_positions[query_index][position];
+        positions.push(position);
+    }
+
+    positions.reverse();
+
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Match the query against any part of the candidate allowing for a few typos, for when
+/// `fuzzy_match` finds nothing
+pub fn typo_tolerant_match(
+    query: &str,
+    candidate: &str,
+    case_sensitivity: CaseSensitivity,
+) -> Option<FuzzyMatch> {
+    let case_sensitive = is_case_sensitive(query, case_sensitivity);
+    let query = normalize(query.trim(), case_sensitive);
+    let candidate = normalize(candidate, case_sensitive);
+    let allowed_typos = match query.len() {
+        0..=2 => return None,
+        3..=5 => 1,
+        _ => 2,
+    };
+
+    // Edit distance between the query and the best matching substring of the candidate,
+    // counting swapped neighbours as a single typo
+    let mut distances = vec![vec![0; candidate.len() + 1]; query.len() + 1];
+
+    for (query_index, row) in distances.iter_mut().enumerate() {
+        row[0] = query_index;
+    }
+
+    for query_index in 1..=query.len() {
+        for candidate_index in 1..=candidate.len() {
+            let substitution_cost =
+                usize::from(query[query_index - 1] != candidate[candidate_index - 1]);
+            let mut distance = (distances[query_index - 1][candidate_index] + 1)
+                .min(distances[query_index][candidate_index - 1] + 1)
+                .min(distances[query_index - 1][candidate_index - 1] + substitution_cost);
+
+            if query_index > 1
+                && candidate_index > 1
+                && query[query_index - 1] == candidate[candidate_index - 2]
+                && query[query_index - 2] == candidate[candidate_index - 1]
+            {
+                distance = distance.min(distances[query_index - 2][candidate_index - 2] + 1);
+            }
+
+            distances[query_index][candidate_index] = distance;
+        }
+    }
+
+    let (mut candidate_index, typos) = distances[query.len()]
+        .iter()
+        .cloned()
+        .enumerate()
+        .min_by_key(|(_, distance)| *distance)?;
+
+    if typos > allowed_typos {
+        return None;
+    }
+
+    // Walk back through the distances to find which characters of the candidate were matched,
+    // so typo matches can be highlighted too
+    let mut query_index = query.len();
+    let mut positions = vec![];
+
+    while query_index > 0 {
+        let distance = distances[query_index][candidate_index];
+
+        if candidate_index > 0
+            && query[query_index - 1] == candidate[candidate_index - 1]
+            && distances[query_index - 1][candidate_index - 1] == distance
+        {
+            positions.push(candidate_index - 1);
+            query_index -= 1;
+            candidate_index -= 1;
+        } else if query_index > 1
+            && candidate_index > 1
+            && query[query_index - 1] == candidate[candidate_index - 2]
+            && query[query_index - 2] == candidate[candidate_index - 1]
+            && distances[query_index - 2][candidate_index - 2] + 1 == distance
+        {
+            positions.extend([candidate_index - 1, candidate_index - 2]);
+            query_index -= 2;
+            candidate_index -= 2;
+        } else if candidate_index > 0
+            && distances[query_index - 1][candidate_index - 1] + 1 == distance
+        {
+            // A substituted character
+            query_index -= 1;
+            candidate_index -= 1;
+        } else if distances[query_index - 1][candidate_index] + 1 == distance {
+            // A character of the query that's missing in the candidate
+            query_index -= 1;
+        } else {
+            // An extra character in the candidate
+            candidate_index -= 1;
+        }
+    }
+
+    positions.reverse();
+
+    Some(FuzzyMatch {
+        score: (query.len() - typos) as i64 * SCORE_MATCH,
+        positions,
+    })
+}
+
+/// Highlight the characters at the given positions
+pub fn highlight_positions(text: &str, positions: &[usize]) -> String {
+    text.chars()
+        .enumerate()
+        .map(|(index, character)| {
+            if positions.contains(&index) {
+                character.to_string().yellow().bold().to_string()
+            } else {
+                character.to_string()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_match_finds_word_starts() {
+        let word_match =
+            fuzzy_match("cpf", "customer-portal-frontend", CaseSensitivity::Smart).unwrap();
+
+        assert_eq!(word_match.positions, vec![0, 9, 16]);
+        assert!(fuzzy_match("cpf", "customer-portal", CaseSensitivity::Smart).is_none());
+    }
+
+    #[test]
+    fn fuzzy_match_scores() {
+        let score = |query: &str, candidate: &str| {
+            fuzzy_match(query, candidate, CaseSensitivity::Smart)
+                .unwrap()
+                .score
+        };
+
+        // Word starts beat characters in the middle of a word
+        assert!(score("cpf", "customer-portal-frontend") > score("cpf", "cli-platform"));
+        // Consecutive characters beat scattered ones
+        assert!(score("api", "api-gateway") > score("api", "a-pretty-idea"));
+        // A word after a delimiter beats the same letters inside a word
+        assert!(score("portal", "customer-portal") > score("portal", "supportal"));
+    }
+
+    #[test]
+    fn fuzzy_match_case_sensitivity() {
+        assert!(fuzzy_match("CPF", "customer-portal-frontend", CaseSensitivity::Smart).is_none());
+        assert!(fuzzy_match(
+            "CPF",
+            "customer-portal-frontend",
+            CaseSensitivity::Sensitive
+        )
+        .is_none());
+        assert!(fuzzy_match(
+            "CPF",
+            "customer-portal-frontend",
+            CaseSensitivity::Insensitive
+        )
+        .is_some());
+        assert!(fuzzy_match("cpf", "Customer-Portal-Frontend", CaseSensitivity::Smart).is_some());
+        assert!(fuzzy_match(
+            "cpf",
+            "Customer-Portal-Frontend",
+            CaseSensitivity::Sensitive
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn typo_tolerant_match_positions() {
+        let typo_match =
+            typo_tolerant_match("cutsomer", "customer-portal", CaseSensitivity::Smart).unwrap();
+
+        assert_eq!(typo_match.score, 7 * SCORE_MATCH);
+        assert_eq!(typo_match.positions, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+
+        // The substituted character isn't highlighted
+        let typo_match =
+            typo_tolerant_match("portel", "customer-portal", CaseSensitivity::Smart).unwrap();
+
+        assert_eq!(typo_match.positions, vec![9, 10, 11, 12, 14]);
+        assert!(typo_tolerant_match("xyzzy", "customer-portal", CaseSensitivity::Smart).is_none());
+    }
+
+    #[test]
+    fn highlight_positions_highlights_characters() {
+        colored::control::set_override(true);
+
+        assert_eq!(
+            highlight_positions("api", &[0, 2]),
+            format!("{}p{}", "a".yellow().bold(), "i".yellow().bold())
+        );
+        assert_eq!(highlight_positions("api", &[]), "api");
+        assert!(highlight_positions("api", &[1]).contains("\u{1b}[1;33mp\u{1b}[0m"));
+    }
+}
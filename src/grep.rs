@@ -0,0 +1,146 @@
+use clap::Args;
+use colored::Colorize;
+use log::error;
+use rayon::prelude::*;
+use regex::{Regex, RegexBuilder};
+
+use crate::config::UserConfigSchema;
+use crate::{clean, metadata, projects, stats};
+
+#[derive(Args)]
+pub struct GrepArgs {
+    /// Regular expression to search for
+    pub pattern: String,
+
+    /// Only search projects of this version
+    #[clap(long)]
+    pub version: Option<String>,
+
+    /// Only search projects with this tag
+    #[clap(short, long)]
+    pub tag: Vec<String>,
+
+    /// Only print the names of matching files
+    #[clap(short = 'l', long)]
+    pub files_with_matches: bool,
+
+    /// Search case-insensitively
+    #[clap(short, long)]
+    pub ignore_case: bool,
+}
+
+struct FileMatches {
+    path: String,
+    lines: Vec<(usize, String)>,
+}
+
+fn highlight_matches(line: &str, pattern: &Regex) -> String {
+    let mut highlighted_line = String::new();
+    let mut last_end = 0;
+
+    for pattern_match in pattern.find_iter(line) {
+        highlighted_line.push_str(&line[last_end..pattern_match.start()]);
+        highlighted_line.push_str(&pattern_match.as_str().red().bold().to_string());
+        last_end = pattern_match.end();
+    }
+
+    highlighted_line.push_str(&line[last_end..]);
+    highlighted_line
+}
+
+pub fn grep_projects(config: &UserConfigSchema, grep_args: &GrepArgs) {
+    let pattern = RegexBuilder::new(&grep_args.pattern)
+        .case_insensitive(grep_args.ignore_case)
+        .build()
+        .unwrap_or_else(|e| {
+            error!("Invalid pattern: {}", e);
+            std::process::exit(1)
+        });
+    let mut found_matches = false;
+
+    for (project_name, project_root) in projects::get_project_roots(config) {
+        if !metadata::has_tags(
+            &metadata::get_project_tags(&project_name, &project_root),
+            &grep_args.tag,
+        ) {
+            continue;
+        }
+
+        let project = match projects::get_project_or_skip(&project_root) {
+            Some(project) => project,
+            None => continue,
+        };
+
+        if let Some(version_name) = &grep_args.version {
+            if !project
+                .versions
+                .iter()
+                .any(|version| version.version.eq_ignore_ascii_case(version_name))
+            {
+                continue;
+            }
+        }
+
+        let project_files =
+            stats::get_project_files(&project.root, &clean::get_artifact_directories(&project));
+        let mut file_matches: Vec<FileMatches> = project_files
+            .par_iter()
+            .filter_map(|path| {
+                // Binary files and files that aren't valid UTF-8 can't be read and are skipped
+                let content = std::fs::read_to_string(path).ok()?;
+                let lines: Vec<(usize, String)> = content
+                    .lines()
+                    .enumerate()
+                    .filter(|(_, line)| pattern.is_match(line))
+                    .map(|(line_index, line)| (line_index + 1, line.to_string()))
+                    .collect();
+
+                if lines.is_empty() {
+                    return None;
+                }
+
+                Some(FileMatches {
+                    path: path
+                        .strip_prefix(&project.root)
+                        .unwrap_or(path)
+                        .to_string_lossy()
+                        .into_owned(),
+                    lines,
+                })
+            })
+            .collect();
+
+        if file_matches.is_empty() {
+            continue;
+        }
+
+        file_matches.sort_by(|a, b| a.path.cmp(&b.path));
+
+        if found_matches {
+            println!();
+        }
+
+        found_matches = true;
+        println!("{}", project.name.bold().underline());
+
+        for file_match in file_matches {
+            if grep_args.files_with_matches {
+                println!("{}", file_match.path.purple());
+                continue;
+            }
+
+            for (line_number, line) in file_match.lines {
+                println!(
+                    "{}:{}:{}",
+                    file_match.path.purple(),
+                    line_number.to_string().green(),
+                    highlight_matches(line.trim_end(), &pattern)
+                );
+            }
+        }
+    }
+
+    if !found_matches {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{Local, TimeZone};
+use colored::Colorize;
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::{config, HistoryArgs};
+
+#[derive(Deserialize, Serialize)]
+pub struct HistoryRecord {
+    pub project: String,
+    pub version: String,
+    pub tool: String,
+    pub task: String,
+    pub arguments: Vec<String>,
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub exit_code: i32,
+}
+
+impl HistoryRecord {
+    pub fn new(project: &str, version: &str, tool: &str, arguments: &[String]) -> HistoryRecord {
+        HistoryRecord {
+            project: project.to_string(),
+            version: version.to_string(),
+            tool: tool.to_string(),
+            task: arguments.first().cloned().unwrap_or_default(),
+            arguments: arguments.iter().skip(1).cloned().collect(),
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            duration_ms: 0,
+            exit_code: 0,
+        }
+    }
+
+    /// Fill in how the execution ended and append the record to the history
+    pub fn finish(mut self, duration: Duration, exit_code: i32) {
+        self.duration_ms = duration.as_millis() as u64;
+        self.exit_code = exit_code;
+
+        append_history_record(&self);
+    }
+}
+
+pub fn get_history_path() -> PathBuf {
+    let mut history_path = config::get_config_directory();
+    history_path.push("history.jsonl");
+
+    history_path
+}
+
+pub fn append_history_record(record: &HistoryRecord) {
+    let record_line = serde_json::to_string(record).unwrap_or_else(|_| {
+        error!("Unable to convert history record to JSON");
+        std::process::exit(1)
+    });
+    let mut history_file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_history_path())
+        .unwrap_or_else(|_| {
+            error!("Unable to open history file");
+            std::process::exit(1)
+        });
+
+    writeln!(history_file, "{}", record_line).unwrap_or_else(|_| {
+        error!("Unable to write history file");
+        std::process::exit(1)
+    });
+}
+
+pub fn read_history() -> Vec<HistoryRecord> {
+    let history_path = get_history_path();
+
+    if !history_path.exists() {
+        return vec![];
+    }
+
+    let history_content = std::fs::read_to_string(history_path).unwrap_or_else(|_| {
+        error!("Unable to read history file");
+        std::process::exit(1)
+    });
+
+    // Skip lines that can't be parsed, e.g. from an execution that was interrupted while writing
+    history_content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Point the history records of a project to its new name
+pub fn rename_project_history(old_project_name: &str, new_project_name: &str) {
+    let mut history = read_history();
+
+    if !history
+        .iter()
+        .any(|record| record.project == old_project_name)
+    {
+        return;
+    }
+
+    for record in history.iter_mut() {
+        if record.project == old_project_name {
+            record.project = new_project_name.to_string();
+        }
+    }
+
+    let history_content: String = history
+        .iter()
+        .filter_map(|record| serde_json::to_string(record).ok())
+        .map(|record_line| format!("{}\n", record_line))
+        .collect();
+
+    std::fs::write(get_history_path(), history_content).unwrap_or_else(|_| {
+        error!("Unable to write history file");
+        std::process::exit(1)
+    });
+}
+
+pub fn show_history(history_args: &HistoryArgs) {
+    let history: Vec<HistoryRecord> = read_history()
+        .into_iter()
+        .filter(|record| match &history_args.project {
+            Some(project) => &record.project == project,
+            None => true,
+        })
+        .filter(|record| match &history_args.task {
+            Some(task) => &record.task == task,
+            None => true,
+        })
+        .collect();
+
+    if history.is_empty() {
+        println!("No executions found");
+        return;
+    }
+
+    let amount = history_args.amount.unwrap_or(10);
+
+    println!("{}", "Recent executions:".bold().underline());
+    println!();
+
+    for record in history.iter().rev().take(amount) {
+        let started_at = Local
+            .timestamp_opt(record.started_at as i64, 0)
+            .single()
+            .map(|started_at| started_at.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let command_string = std::iter::once(&record.task)
+            .chain(&record.arguments)
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(" ");
+        let exit_code_string = if record.exit_code == 0 {
+            "ok".green()
+        } else {
+            format!("exit {}", record.exit_code).red()
+        };
+
+        println!(
+            "{} {} {} ({}, {})",
+            started_at,
+            record.project.bold(),
+            command_string,
+            format_duration(record.duration_ms),
+            exit_code_string
+        );
+    }
+
+    let mut records_by_task: BTreeMap<(&str, &str), Vec<&HistoryRecord>> = BTreeMap::new();
+
+    for record in &history {
+        records_by_task
+            .entry((&record.project, &record.task))
+            .or_default()
+            .push(record);
+    }
+
+    println!();
+    println!("{}", "Summary:".bold().underline());
+    println!();
+
+    for ((project, task), records) in records_by_task {
+        let mut durations: Vec<u64> = records.iter().map(|record| record.duration_ms).collect();
+        let failures = records
+            .iter()
+            .filter(|record| record.exit_code != 0)
+            .count();
+
+        durations.sort();
+
+        let average = durations.iter().sum::<u64>() / durations.len() as u64;
+        let p95_index = ((durations.len() as f64 * 0.95).ceil() as usize).max(1) - 1;
+
+        println!(
+            "{} {}: {} {}, avg {}, p95 {}, {:.0}% failed",
+            project.bold(),
+            task,
+            durations.len(),
+            if durations.len() == 1 { "run" } else { "runs" },
+            format_duration(average),
+            format_duration(durations[p95_index]),
+            failures as f64 / durations.len() as f64 * 100.0
+        );
+    }
+}
+
+pub fn format_duration(duration_ms: u64) -> String {
+    if duration_ms < 1000 {
+        format!("{}ms", duration_ms)
+    } else if duration_ms < 60_000 {
+        format!("{:.1}s", duration_ms as f64 / 1000.0)
+    } else {
+        format!("{}m {}s", duration_ms / 60_000, duration_ms % 60_000 / 1000)
+    }
+}
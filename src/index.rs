@@ -0,0 +1,484 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use colored::Colorize;
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{self, UserConfigSchema};
+use crate::project_config::{self, PROJECT_CONFIG_FILE};
+use crate::{aliases, metadata, projects, FindArgs};
+
+const README_FILES: [&str; 4] = ["README.md", "README", "readme.md", "README.txt"];
+const SNIPPET_LENGTH: usize = 100;
+
+#[derive(Deserialize, Serialize, Default)]
+pub struct SearchIndexSchema {
+    #[serde(default)]
+    pub projects: BTreeMap<String, IndexedProjectSchema>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct IndexedProjectSchema {
+    /// Changes whenever one of the indexed files, the tags or the description change
+    pub fingerprint: String,
+    pub documents: Vec<IndexedDocumentSchema>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct IndexedDocumentSchema {
+    pub source: String,
+    pub text: String,
+}
+
+struct SearchResult {
+    project_name: String,
+    score: f64,
+    snippet: Option<(String, String)>,
+}
+
+pub fn get_index_path() -> PathBuf {
+    let mut index_path = config::get_config_directory();
+    index_path.push("cache");
+    index_path.push("index.json");
+
+    index_path
+}
+
+fn read_index() -> SearchIndexSchema {
+    std::fs::read_to_string(get_index_path())
+        .ok()
+        .and_then(|index_content| serde_json::from_str(&index_content).ok())
+        .unwrap_or_default()
+}
+
+fn write_index(index: &SearchIndexSchema) {
+    let index_path = get_index_path();
+    let index_content = serde_json::to_string(index).unwrap_or_else(|_| {
+        error!("Unable to convert search index to JSON");
+        std::process::exit(1)
+    });
+
+    if let Some(index_directory) = index_path.parent() {
+        let _ = std::fs::create_dir_all(index_directory);
+    }
+
+    std::fs::write(index_path, index_content).unwrap_or_else(|_| {
+        error!("Unable to write search index");
+        std::process::exit(1)
+    });
+}
+
+/// Lowercase words of at least two characters
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|character: char| !character.is_alphanumeric())
+        .filter(|word| word.chars().count() > 1)
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+pub fn get_readme_path(project_root: &Path) -> Option<PathBuf> {
+    README_FILES
+        .iter()
+        .map(|readme_file| project_root.join(readme_file))
+        .find(|readme_path| readme_path.is_file())
+}
+
+fn get_source_files(project_root: &Path) -> Vec<PathBuf> {
+    let mut source_files: Vec<PathBuf> = get_readme_path(project_root).into_iter().collect();
+
+    for manifest_file in ["Cargo.toml", "package.json", PROJECT_CONFIG_FILE] {
+        let manifest_path = project_root.join(manifest_file);
+
+        if manifest_path.is_file() {
+            source_files.push(manifest_path);
+        }
+    }
+
+    source_files
+}
+
+fn get_fingerprint(
+    source_files: &[PathBuf],
+    project_metadata: &metadata::ProjectMetadataSchema,
+) -> String {
+    let mut fingerprint = String::new();
+
+    for source_file in source_files {
+        let (modified, size) = std::fs::metadata(source_file)
+            .map(|file_metadata| {
+                (
+                    file_metadata
+                        .modified()
+                        .ok()
+                        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                        .map(|modified| modified.as_nanos())
+                        .unwrap_or(0),
+                    file_metadata.len(),
+                )
+            })
+            .unwrap_or((0, 0));
+
+        fingerprint.push_str(&format!("{}:{}:{};", source_file.display(), modified, size));
+    }
+
+    fingerprint.push_str(&project_metadata.tags.join(","));
+    fingerprint.push(';');
+    fingerprint.push_str(project_metadata.description.as_deref().unwrap_or(""));
+    fingerprint
+}
+
+fn get_manifest_description(manifest_path: &Path) -> Option<String> {
+    let manifest_content = std::fs::read_to_string(manifest_path).ok()?;
+
+    if manifest_path.ends_with("package.json") {
+        let manifest: serde_json::Value = serde_json::from_str(&manifest_content).ok()?;
+
+        return manifest["description"]
+            .as_str()
+            .map(|description| description.to_string());
+    }
+
+    let manifest: toml::Value = toml::from_str(&manifest_content).ok()?;
+
+    manifest
+        .get("package")
+        .and_then(|package| package.get("description"))
+        .and_then(|description| description.as_str())
+        .map(|description| description.to_string())
+}
+
+fn index_project(
+    project_name: &str,
+    project_root: &Path,
+    source_files: &[PathBuf],
+    project_metadata: &metadata::ProjectMetadataSchema,
+) -> Vec<IndexedDocumentSchema> {
+    let mut documents: Vec<IndexedDocumentSchema> = vec![];
+    let tags = metadata::get_project_tags(project_name, project_root);
+
+    if !tags.is_empty() {
+        documents.push(IndexedDocumentSchema {
+            source: "tags".to_string(),
+            text: tags.join(", "),
+        });
+    }
+
+    let project_config_description = project_config::try_read_project_config(project_root)
+        .ok()
+        .flatten()
+        .and_then(|project_config| project_config.description);
+
+    if let Some(description) = project_metadata
+        .description
+        .clone()
+        .or(project_config_description)
+    {
+        documents.push(IndexedDocumentSchema {
+            source: "description".to_string(),
+            text: description,
+        });
+    }
+
+    for source_file in source_files {
+        let source = source_file
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+
+        if source == PROJECT_CONFIG_FILE {
+            continue;
+        }
+
+        let text = if README_FILES.contains(&source.as_str()) {
+            std::fs::read_to_string(source_file).ok()
+        } else {
+            get_manifest_description(source_file)
+        };
+
+        if let Some(text) = text {
+            documents.push(IndexedDocumentSchema { source, text });
+        }
+    }
+
+    documents
+}
+
+/// Bring the index up to date. Only projects whose indexed files, tags or description changed
+/// are read again, projects that no longer exist are dropped.
+pub fn update_index(config: &UserConfigSchema) -> SearchIndexSchema {
+    let mut index = read_index();
+    let all_metadata = metadata::read_metadata();
+    let mut updated_index = SearchIndexSchema::default();
+    let mut index_changed = false;
+
+    for (project_name, project_root) in projects::get_project_roots(config) {
+        let project_metadata = all_metadata
+            .projects
+            .get(&project_name)
+            .cloned()
+            .unwrap_or_default();
+        let source_files = get_source_files(&project_root);
+        let fingerprint = get_fingerprint(&source_files, &project_metadata);
+        let indexed_project = match index.projects.remove(&project_name) {
+            Some(indexed_project) if indexed_project.fingerprint == fingerprint => indexed_project,
+            _ => {
+                index_changed = true;
+
+                IndexedProjectSchema {
+                    documents: index_project(
+                        &project_name,
+                        &project_root,
+                        &source_files,
+                        &project_metadata,
+                    ),
+                    fingerprint,
+                }
+            }
+        };
+
+        updated_index.projects.insert(project_name, indexed_project);
+    }
+
+    if index_changed || !index.projects.is_empty() {
+        write_index(&updated_index);
+    }
+
+    updated_index
+}
+
+/// How well a document term matches a query term. Prefixes count half, so "invoice" finds
+/// "invoices".
+fn get_term_match(term: &str, query_term: &str) -> f64 {
+    if term == query_term {
+        1.0
+    } else if term.starts_with(query_term) {
+        0.5
+    } else {
+        0.0
+    }
+}
+
+fn get_document_weight(source: &str) -> f64 {
+    match source {
+        "tags" => 2.0,
+        "description" => 1.5,
+        _ => 1.0,
+    }
+}
+
+/// Clean up a line of markdown for display
+fn clean_snippet_line(line: &str) -> String {
+    line.trim()
+        .trim_start_matches(['#', '*', '-', '>', ' '])
+        .replace("**", "")
+        .replace('`', "")
+}
+
+fn highlight_query_terms(text: &str, query_terms: &[String]) -> String {
+    let mut highlighted_text = String::new();
+    let mut word = String::new();
+
+    for character in text.chars().chain(std::iter::once(' ')) {
+        if character.is_alphanumeric() {
+            word.push(character);
+            continue;
+        }
+
+        if !word.is_empty() {
+            let lowercase_word = word.to_lowercase();
+
+            if query_terms
+                .iter()
+                .any(|query_term| lowercase_word.starts_with(query_term.as_str()))
+            {
+                highlighted_text.push_str(&word.yellow().bold().to_string());
+            } else {
+                highlighted_text.push_str(&word);
+            }
+
+            word.clear();
+        }
+
+        highlighted_text.push(character);
+    }
+
+    highlighted_text.pop();
+    highlighted_text
+}
+
+/// The line with the most query terms, shortened around the first match
+fn get_snippet(
+    documents: &[IndexedDocumentSchema],
+    query_terms: &[String],
+) -> Option<(String, String)> {
+    let mut best_snippet: Option<(f64, &str, String)> = None;
+
+    for document in documents {
+        for line in document.text.lines() {
+            let line = clean_snippet_line(line);
+            let line_score: f64 = tokenize(&line)
+                .iter()
+                .map(|term| {
+                    query_terms
+                        .iter()
+                        .map(|query_term| get_term_match(term, query_term))
+                        .fold(0.0, f64::max)
+                })
+                .sum::<f64>()
+                * get_document_weight(&document.source);
+
+            if line_score > 0.0
+                && best_snippet
+                    .as_ref()
+                    .is_none_or(|(best_score, _, _)| line_score > *best_score)
+            {
+                best_snippet = Some((line_score, &document.source, line));
+            }
+        }
+    }
+
+    let (_, source, line) = best_snippet?;
+    let characters: Vec<char> = line.chars().collect();
+    let lowercase_line = line.to_lowercase();
+    let first_match = query_terms
+        .iter()
+        .filter_map(|query_term| lowercase_line.find(query_term.as_str()))
+        .min()
+        .map(|byte_index| lowercase_line[..byte_index].chars().count())
+        .unwrap_or(0);
+    let start = first_match.saturating_sub(SNIPPET_LENGTH / 3);
+    let end = (start + SNIPPET_LENGTH).min(characters.len());
+    let mut snippet: String = characters[start..end].iter().collect();
+
+    if start > 0 {
+        snippet = format!("…{}", snippet);
+    }
+
+    if end < characters.len() {
+        snippet.push('…');
+    }
+
+    Some((
+        source.to_string(),
+        highlight_query_terms(&snippet, query_terms),
+    ))
+}
+
+fn search_index(index: &SearchIndexSchema, query: &str) -> Vec<SearchResult> {
+    let query_terms: Vec<String> = tokenize(query)
+        .into_iter()
+        .collect::<HashSet<String>>()
+        .into_iter()
+        .collect();
+    let project_terms: BTreeMap<&String, Vec<(String, f64)>> = index
+        .projects
+        .iter()
+        .map(|(project_name, indexed_project)| {
+            let terms = indexed_project
+                .documents
+                .iter()
+                .flat_map(|document| {
+                    let document_weight = get_document_weight(&document.source);
+
+                    tokenize(&document.text)
+                        .into_iter()
+                        .map(move |term| (term, document_weight))
+                })
+                .collect();
+
+            (project_name, terms)
+        })
+        .collect();
+    let projects_count = index.projects.len() as f64;
+    let mut search_results: Vec<SearchResult> = vec![];
+
+    for (project_name, terms) in &project_terms {
+        let mut score = 0.0;
+
+        for query_term in &query_terms {
+            let matching_projects_count = project_terms
+                .values()
+                .filter(|terms| {
+                    terms
+                        .iter()
+                        .any(|(term, _)| get_term_match(term, query_term) > 0.0)
+                })
+                .count() as f64;
+            let term_frequency: f64 = terms
+                .iter()
+                .map(|(term, document_weight)| get_term_match(term, query_term) * document_weight)
+                .sum();
+
+            if term_frequency == 0.0 {
+                continue;
+            }
+
+            // Rare terms weigh more, and repeating a term has diminishing returns
+            let inverse_document_frequency = (1.0 + projects_count / matching_projects_count).ln();
+
+            score += inverse_document_frequency * term_frequency / (term_frequency + 1.0);
+        }
+
+        if score > 0.0 {
+            search_results.push(SearchResult {
+                project_name: project_name.to_string(),
+                score,
+                snippet: get_snippet(&index.projects[*project_name].documents, &query_terms),
+            });
+        }
+    }
+
+    search_results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    search_results
+}
+
+pub fn find_projects_by_content(config: &UserConfigSchema, find_args: &FindArgs) {
+    let index = update_index(config);
+    let amount = find_args.amount.unwrap_or(5);
+    let mut search_results: Vec<SearchResult> = search_index(&index, &find_args.project)
+        .into_iter()
+        .filter(|search_result| {
+            find_args.tag.is_empty()
+                || projects::get_project_root(config, &search_result.project_name).is_some_and(
+                    |project_root| {
+                        metadata::has_tags(
+                            &metadata::get_project_tags(&search_result.project_name, &project_root),
+                            &find_args.tag,
+                        )
+                    },
+                )
+        })
+        .collect();
+
+    if search_results.is_empty() {
+        error!("No project matching \"{}\" found", find_args.project);
+        return;
+    }
+
+    search_results
+        .sort_by_key(|search_result| !aliases::is_pinned(config, &search_result.project_name));
+
+    if !find_args.compact {
+        println!(
+            "{}",
+            format!("Search results for \"{}\":", find_args.project)
+                .bold()
+                .underline()
+        );
+    }
+
+    for search_result in search_results.iter().take(amount) {
+        println!("{}", search_result.project_name);
+
+        if find_args.compact {
+            continue;
+        }
+
+        if let Some((source, snippet)) = &search_result.snippet {
+            println!("  {}: {}", source.dimmed(), snippet);
+        }
+    }
+}
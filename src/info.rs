@@ -0,0 +1,375 @@
+use std::path::Path;
+use std::process::Command;
+
+use chrono::{Local, TimeZone};
+use clap::ValueEnum;
+use colored::Colorize;
+use log::error;
+
+use crate::config::UserConfigSchema;
+use crate::project_config::PROJECT_CONFIG_FILE;
+use crate::projects::{self, Project};
+use crate::{archive, clean, editor, index, metadata, resolve, tasks, InfoArgs};
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum InfoSection {
+    Root,
+    Versions,
+    Tags,
+    Readme,
+    Git,
+    Modified,
+    Size,
+    Tool,
+    Editor,
+    Tasks,
+    Env,
+}
+
+/// Sections that walk the whole project, shown only with --full or --only
+const EXPENSIVE_SECTIONS: [InfoSection; 2] = [InfoSection::Modified, InfoSection::Size];
+
+/// The sections to show: the cheap ones (or all of them with --full), or only the ones passed with
+/// --only, minus the ones passed with --hide
+fn get_sections(info_args: &InfoArgs) -> Vec<InfoSection> {
+    InfoSection::value_variants()
+        .iter()
+        .filter(|section| {
+            if info_args.only.is_empty() {
+                info_args.full || !EXPENSIVE_SECTIONS.contains(section)
+            } else {
+                info_args.only.contains(section)
+            }
+        })
+        .filter(|section| !info_args.hide.contains(section))
+        .cloned()
+        .collect()
+}
+
+fn pluralize(count: usize, singular: &str, plural: &str) -> String {
+    format!("{} {}", count, if count == 1 { singular } else { plural })
+}
+
+fn run_git(project_root: &Path, arguments: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(arguments)
+        .current_dir(project_root)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string(),
+    )
+}
+
+/// The first paragraph of the README, skipping headings, badges and HTML
+fn get_readme_paragraph(project_root: &Path) -> Option<String> {
+    let readme_content = std::fs::read_to_string(index::get_readme_path(project_root)?).ok()?;
+    let is_decoration = |line: &str| {
+        ["#", "![", "[![", "<", "---", "==="]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+    };
+    let paragraph: Vec<&str> = readme_content
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty() || is_decoration(line))
+        .take_while(|line| !line.is_empty() && !is_decoration(line))
+        .collect();
+
+    if paragraph.is_empty() {
+        return None;
+    }
+
+    Some(paragraph.join(" "))
+}
+
+fn show_versions(project: &Project) {
+    if project.versions.len() > 1 {
+        println!("{}", format!("{} Versions:", project.versions.len()).bold());
+
+        for version in &project.versions {
+            println!("{} - {}", version.version, version.description);
+        }
+    } else {
+        println!(
+            "{}",
+            format!("Version: {}", project.versions[0].version).bold()
+        );
+        println!("{}", project.versions[0].description);
+
+        if project
+            .config
+            .as_ref()
+            .is_some_and(|project_config| project_config.version.is_some())
+        {
+            println!("Pinned in {}", PROJECT_CONFIG_FILE);
+        }
+    }
+}
+
+fn show_git(project: &Project) {
+    // The first line holds the branch, e.g. "## main...origin/main [ahead 1]" or
+    // "## No commits yet on main", the other lines the changed files
+    let Some(git_status) = run_git(&project.root, &["status", "--porcelain", "--branch"]) else {
+        return;
+    };
+    let mut status_lines = git_status.lines();
+    let branch_line = status_lines
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("## ");
+    let changed_files = status_lines.count();
+    let branch = if branch_line.starts_with("HEAD (no branch)") {
+        run_git(&project.root, &["rev-parse", "--short", "HEAD"])
+            .map(|commit| format!("detached at {}", commit))
+            .unwrap_or_default()
+    } else {
+        branch_line
+            .trim_start_matches("No commits yet on ")
+            .trim_start_matches("Initial commit on ")
+            .split("...")
+            .next()
+            .and_then(|branch| branch.split(' ').next())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    println!("{}", format!("Git: {}", branch).bold());
+
+    if let Some(git_remote) = archive::get_git_remote(&project.root) {
+        println!("Remote: {}", git_remote);
+    }
+
+    if changed_files == 0 {
+        println!("No uncommitted changes");
+    } else {
+        println!(
+            "{}",
+            pluralize(changed_files, "changed file", "changed files")
+        );
+    }
+}
+
+fn show_tasks(project: &Project, project_management_tool: Option<&str>) {
+    let project_tasks = project.get_tasks();
+    let available_tasks = tasks::get_available_tasks(
+        &project.name,
+        &project.root,
+        &project_tasks,
+        project_management_tool,
+    );
+
+    if available_tasks.is_empty() {
+        return;
+    }
+
+    println!(
+        "{}",
+        format!("{}:", pluralize(available_tasks.len(), "Task", "Tasks")).bold()
+    );
+
+    for task in &available_tasks {
+        let task_origin = if !project_tasks.contains_key(&task.name) {
+            "project management tool".to_string()
+        } else if project
+            .config
+            .as_ref()
+            .and_then(|project_config| project_config.tasks.as_ref())
+            .is_some_and(|project_config_tasks| project_config_tasks.contains_key(&task.name))
+        {
+            PROJECT_CONFIG_FILE.to_string()
+        } else {
+            format!("version {}", project.versions[0].version)
+        };
+
+        println!("{} (from {})", task.name, task_origin);
+    }
+}
+
+/// Show the given sections of a project. Tasks of the project management tool are only discovered
+/// when `discover_tool_tasks` is set, since that runs the tool.
+pub fn show_project_info(
+    config: &UserConfigSchema,
+    project: &Project,
+    sections: &[InfoSection],
+    discover_tool_tasks: bool,
+) {
+    println!(
+        "{}",
+        format!("Project: {}", project.name).bold().underline()
+    );
+
+    if let Some(description) = project.get_description() {
+        println!("{}", description);
+    }
+
+    let project_version = &project.versions[0];
+    let (project_management_tool, tool_origin) = match &project_version.project_management_tool {
+        Some(project_management_tool) => (
+            project_management_tool.as_str(),
+            format!("version {}", project_version.version),
+        ),
+        None => (
+            config.project_management_tool.as_str(),
+            "user config".to_string(),
+        ),
+    };
+
+    for section in sections {
+        match section {
+            InfoSection::Root => {
+                println!("{}", format!("Root: {}", project.root.display()).bold());
+            }
+            InfoSection::Versions => show_versions(project),
+            InfoSection::Tags => {
+                let project_tags = metadata::get_project_tags(&project.name, &project.root);
+
+                if !project_tags.is_empty() {
+                    println!("{}", format!("Tags: {}", project_tags.join(", ")).bold());
+                }
+            }
+            InfoSection::Readme => {
+                if let Some(readme_paragraph) = get_readme_paragraph(&project.root) {
+                    println!("{}", "README:".bold());
+                    println!("{}", readme_paragraph);
+                }
+            }
+            InfoSection::Git => show_git(project),
+            InfoSection::Modified => {
+                let last_modified = Local
+                    .timestamp_opt(
+                        clean::get_last_modified(
+                            &project.root,
+                            &clean::get_artifact_directories(project),
+                        ) as i64,
+                        0,
+                    )
+                    .single()
+                    .map(|last_modified| last_modified.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+
+                println!("{}", format!("Last modified: {}", last_modified).bold());
+            }
+            InfoSection::Size => {
+                println!(
+                    "{}",
+                    format!(
+                        "Size: {}",
+                        clean::format_size(clean::get_directory_size(&project.root))
+                    )
+                    .bold()
+                );
+            }
+            InfoSection::Tool => {
+                let tool_path = project
+                    .root
+                    .join(shellexpand::tilde(project_management_tool).as_ref());
+
+                println!("{}", format!("Tool: {}", project_management_tool).bold());
+
+                if tool_path.exists() {
+                    println!("From {}", tool_origin);
+                } else {
+                    println!("From {}, not found in the project", tool_origin);
+                }
+            }
+            InfoSection::Editor => match editor::resolve_editor(config, &None, Some(project)) {
+                Some((editor, origin)) => {
+                    println!("{}", format!("Editor: {}", editor).bold());
+                    println!("From {}", origin);
+                }
+                None => println!("{}", "Editor: None".bold()),
+            },
+            InfoSection::Tasks => show_tasks(
+                project,
+                (discover_tool_tasks && config.discover_tool_tasks.unwrap_or(true))
+                    .then_some(project_management_tool),
+            ),
+            InfoSection::Env => {
+                if let Some(env) = project
+                    .config
+                    .as_ref()
+                    .and_then(|project_config| project_config.env.as_ref())
+                {
+                    println!(
+                        "{}",
+                        format!(
+                            "{}:",
+                            pluralize(env.len(), "Environment variable", "Environment variables")
+                        )
+                        .bold()
+                    );
+
+                    for key in env.keys() {
+                        println!("{} (from {})", key, PROJECT_CONFIG_FILE);
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn show_info(config: &UserConfigSchema, info_args: &InfoArgs) {
+    let sections = get_sections(info_args);
+    let discover_tool_tasks = info_args.full || info_args.only.contains(&InfoSection::Tasks);
+
+    if info_args.all {
+        let mut has_invalid_projects = false;
+
+        for (project_index, (project_name, project_root)) in
+            projects::get_project_roots(config).into_iter().enumerate()
+        {
+            if project_index > 0 {
+                println!();
+            }
+
+            // A broken project config shouldn't hide all the other projects
+            match projects::try_get_project_for_directory(project_root.to_str()) {
+                Ok(Some(project)) => {
+                    show_project_info(config, &project, &sections, discover_tool_tasks)
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    println!(
+                        "{}",
+                        format!("Project: {}", project_name).bold().underline()
+                    );
+                    error!("{}", e);
+                    has_invalid_projects = true;
+                }
+            }
+        }
+
+        if has_invalid_projects {
+            std::process::exit(1)
+        }
+
+        return;
+    }
+
+    let project = match &info_args.project {
+        Some(project_query) => {
+            let (project_name, project_root) = resolve::resolve_project(config, project_query);
+
+            projects::get_project_for_directory(project_root.to_str()).unwrap_or_else(|| {
+                error!("Could not find project {}", project_name);
+                std::process::exit(1)
+            })
+        }
+        None => projects::get_project_for_directory(info_args.directory.as_deref()).unwrap_or_else(
+            || {
+                error!("Could not find a project in the current directory");
+                std::process::exit(1);
+            },
+        ),
+    };
+
+    show_project_info(config, &project, &sections, discover_tool_tasks);
+}
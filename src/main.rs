@@ -1,17 +1,48 @@
 use std::{env, process::exit};
 
+use aliases::{Alias, AliasCommands, PinArgs};
+use archive::{ArchiveArgs, ArchiveCommands, UnarchiveArgs};
 use clap::{Args, Parser, Subcommand, ValueEnum};
+use clean::CleanArgs;
+use clone::CloneArgs;
+use grep::GrepArgs;
 use log::error;
+use metadata::{Tag, TagCommands};
+use registry::{RegisterArgs, UnregisterArgs};
 use repositories::{Repo, RepositoryCommands};
+use scaffold::NewArgs;
+use stats::StatsArgs;
+use trash::{MvArgs, RmArgs, UndoArgs};
 
 use simplelog::*;
 
+pub mod aliases;
+pub mod archive;
+pub mod clean;
+pub mod clone;
 pub mod config;
+pub mod editor;
+pub mod environment;
+pub mod frecency;
+pub mod fuzzy;
+pub mod grep;
+pub mod history;
+pub mod index;
+pub mod info;
+pub mod metadata;
+pub mod project_config;
 pub mod projects;
+pub mod registry;
 pub mod repositories;
+pub mod resolve;
+pub mod scaffold;
 pub mod shell;
+pub mod stats;
+pub mod tasks;
+pub mod trash;
 pub mod update;
 pub mod versions;
+pub mod watch;
 
 #[derive(Parser)]
 #[command(name = "p")]
@@ -42,27 +73,98 @@ enum Commands {
     Repo(Repo),
     /// Find a project
     Find(FindArgs),
-    /// Open your preferred editor in the current project
+    /// Open your preferred editor in the current or a given project
     Edit(EditArgs),
     /// Update p
     Update(UpdateArgs),
+    /// Show past executions and how long they took
+    History(HistoryArgs),
+    /// Manage the tags of projects
+    Tag(Tag),
+    /// Set the description of a project
+    Describe(DescribeArgs),
+    /// Run a command in the root of every project
+    Each(EachArgs),
+    /// Manage project aliases
+    Alias(Alias),
+    /// Pin a project so it is listed first
+    Pin(PinArgs),
+    /// Unpin a project
+    Unpin(PinArgs),
+    /// Register a project outside of the projects directory
+    Register(RegisterArgs),
+    /// Remove a registered project
+    Unregister(UnregisterArgs),
+    /// Clone a git repository into the projects directory and print its path
+    Clone(CloneArgs),
+    /// Create a new project from the template or scaffold command of a version
+    New(NewArgs),
+    /// Move a project to the archive or list archived projects
+    Archive(ArchiveArgs),
+    /// Restore an archived project
+    Unarchive(UnarchiveArgs),
+    /// Move a project to the trash
+    Rm(RmArgs),
+    /// Rename a project
+    Mv(MvArgs),
+    /// Reverse the last p rm or p mv
+    Undo(UndoArgs),
+    /// Delete the build artifacts of inactive projects
+    Clean(CleanArgs),
+    /// Show the disk usage and lines of code of projects
+    Stats(StatsArgs),
+    /// Search the files of all projects with a regular expression
+    Grep(GrepArgs),
 }
 
-#[derive(Args)]
+#[derive(Args, Default)]
 pub struct InfoArgs {
+    /// Name of the project, instead of the project in the current directory
+    #[clap(conflicts_with = "directory")]
+    project: Option<String>,
+
     /// Which directory to get information of
     #[clap(short, long)]
     directory: Option<String>,
+
+    /// Show information about every project
+    #[clap(long, conflicts_with_all = ["project", "directory"])]
+    all: bool,
+
+    /// Also show the sections that walk the whole project and the tasks of the project management
+    /// tool
+    #[clap(long)]
+    full: bool,
+
+    /// Only show these sections
+    #[clap(long, value_enum, value_delimiter = ',')]
+    only: Vec<info::InfoSection>,
+
+    /// Hide these sections
+    #[clap(long, value_enum, value_delimiter = ',')]
+    hide: Vec<info::InfoSection>,
 }
 
 #[derive(Args)]
-pub struct ListArgs {}
+pub struct ListArgs {
+    /// Only list projects with this tag
+    #[clap(short, long)]
+    tag: Vec<String>,
+
+    /// Include archived projects
+    #[clap(long)]
+    archived: bool,
+}
 
 #[derive(Args)]
 pub struct RepoSyncArgs {}
 
 #[derive(Args)]
 pub struct EditArgs {
+    /// Name of the project to open instead of the current one
+    #[clap(conflicts_with = "here")]
+    project: Option<String>,
+
     /// Path of the editor to use
     #[clap(short, long)]
     editor: Option<String>,
@@ -70,12 +172,57 @@ pub struct EditArgs {
     /// Run editor in a detached process
     #[clap(short, long)]
     detach: bool,
+
+    /// File to open, relative to the project root
+    #[clap(short, long)]
+    file: Option<String>,
+
+    /// Line to open the file at
+    #[clap(short, long, requires = "file")]
+    line: Option<usize>,
+
+    /// Run the editor in the current directory instead of the project root
+    #[clap(long)]
+    here: bool,
 }
 
 #[derive(Args)]
 pub struct ExecuteArgs {
+    /// Name or alias of the project to execute in instead of the current one
+    #[clap(short, long)]
+    project: Option<String>,
+
     /// Execution arguments
+    #[clap(trailing_var_arg = true, allow_hyphen_values = true)]
     arguments: Vec<String>,
+
+    /// Load environment variables from this file instead of the version's env files
+    #[clap(long)]
+    env_file: Vec<String>,
+
+    /// Do not load any environment files
+    #[clap(long, conflicts_with = "env_file")]
+    no_env: bool,
+
+    /// Show which environment variables would be loaded and where they come from
+    #[clap(long)]
+    explain_env: bool,
+
+    /// Run again whenever files in the project root change
+    #[clap(short, long, conflicts_with = "explain_env")]
+    watch: bool,
+
+    /// Only rerun when files matching this glob change
+    #[clap(long, requires = "watch")]
+    glob: Vec<String>,
+
+    /// List the tasks of the version and the project management tool
+    #[clap(short, long)]
+    list: bool,
+
+    /// Only show task names
+    #[clap(short, long, requires = "list")]
+    compact: bool,
 }
 
 #[derive(Args)]
@@ -96,6 +243,26 @@ pub struct FindArgs {
     /// Amount of results to display
     #[clap(short, long)]
     amount: Option<usize>,
+
+    /// Only find projects with this tag
+    #[clap(short, long)]
+    tag: Vec<String>,
+
+    /// Include archived projects
+    #[clap(long)]
+    archived: bool,
+
+    /// Search the READMEs, descriptions and tags of projects instead of their names
+    #[clap(long, conflicts_with = "archived")]
+    content: bool,
+
+    /// How to treat uppercase letters in the query
+    #[clap(long, value_enum, default_value_t)]
+    case: fuzzy::CaseSensitivity,
+
+    /// Match the query against the name or the full path of projects
+    #[clap(long, value_enum, default_value_t)]
+    match_on: fuzzy::MatchOn,
 }
 
 #[derive(Args)]
@@ -113,6 +280,41 @@ pub struct AliasArgs {
 #[derive(Args)]
 pub struct UpdateArgs {}
 
+#[derive(Args)]
+pub struct DescribeArgs {
+    /// Name of the project
+    project: String,
+
+    /// One-line description of the project
+    description: String,
+}
+
+#[derive(Args)]
+pub struct EachArgs {
+    /// Only run in projects with this tag
+    #[clap(short, long)]
+    tag: Vec<String>,
+
+    /// Command to run
+    #[clap(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+    command: Vec<String>,
+}
+
+#[derive(Args)]
+pub struct HistoryArgs {
+    /// Only show executions in this project
+    #[clap(short, long)]
+    project: Option<String>,
+
+    /// Only show executions of this task
+    #[clap(short, long)]
+    task: Option<String>,
+
+    /// Amount of recent executions to display
+    #[clap(short, long)]
+    amount: Option<usize>,
+}
+
 #[derive(ValueEnum, Copy, Clone)]
 pub enum Shell {
     Bash,
@@ -150,25 +352,20 @@ fn main() {
     if cli.command.is_some() {
         match &cli.command.unwrap() {
             Commands::Info(info_args) => {
-                let directory = info_args.directory.as_ref().map(|s| s.as_str());
-
-                projects::get_info_for_project_in_directory(directory);
+                info::show_info(&config, &info_args);
             }
-            Commands::List(_) => {
-                projects::list_projects_in_projects_directory(&config);
+            Commands::List(list_args) => {
+                projects::list_projects_in_projects_directory(&config, list_args);
             }
             Commands::Execute(execute_args) => {
                 projects::execute_in_current_project(&config, &execute_args);
             }
             Commands::Go(go_args) => {
-                let project_path = projects::get_project_path(&config, &go_args);
+                let (project_name, project_root) =
+                    resolve::resolve_project(&config, &go_args.project);
 
-                if project_path.is_some() {
-                    println!("{}", project_path.unwrap().to_string());
-                } else {
-                    error!("Project {} does not exist", go_args.project);
-                    exit(1)
-                }
+                frecency::record_visit(&project_name);
+                println!("{}", project_root.display());
             }
             Commands::Completions(completions_args) => {
                 projects::get_shell_completions(&completions_args)
@@ -180,11 +377,7 @@ fn main() {
                 projects::find_project_in_projects_directory(&config, &find_args)
             }
             Commands::Edit(edit_args) => {
-                projects::open_editor_in_current_project(
-                    &config,
-                    &edit_args.editor,
-                    edit_args.detach,
-                );
+                editor::open_editor_in_current_project(&config, edit_args);
             }
             Commands::Update(_) => {
                 let update_res = update::update();
@@ -194,6 +387,88 @@ fn main() {
                     exit(1);
                 }
             }
+            Commands::History(history_args) => {
+                history::show_history(history_args);
+            }
+            Commands::Tag(tag) => match &tag.command {
+                TagCommands::Add(tag_add_args) => {
+                    metadata::add_tags_to_project(&config, tag_add_args);
+                }
+                TagCommands::Remove(tag_remove_args) => {
+                    metadata::remove_tags_from_project(&config, tag_remove_args);
+                }
+                TagCommands::List(tag_list_args) => {
+                    metadata::list_tags(&config, tag_list_args);
+                }
+            },
+            Commands::Describe(describe_args) => {
+                metadata::describe_project(
+                    &config,
+                    &describe_args.project,
+                    &describe_args.description,
+                );
+            }
+            Commands::Each(each_args) => {
+                projects::run_in_each_project(&config, each_args);
+            }
+            Commands::Alias(alias) => match &alias.command {
+                AliasCommands::Add(alias_add_args) => {
+                    aliases::add_alias_to_config(alias_add_args);
+                }
+                AliasCommands::Remove(alias_remove_args) => {
+                    aliases::remove_alias_from_config(alias_remove_args);
+                }
+                AliasCommands::List(_) => {
+                    aliases::list_aliases(&config);
+                }
+            },
+            Commands::Pin(pin_args) => {
+                aliases::pin_project(pin_args);
+            }
+            Commands::Unpin(pin_args) => {
+                aliases::unpin_project(pin_args);
+            }
+            Commands::Register(register_args) => {
+                registry::register_project(register_args);
+            }
+            Commands::Unregister(unregister_args) => {
+                registry::unregister_project(unregister_args);
+            }
+            Commands::Clone(clone_args) => {
+                clone::clone_project(&config, clone_args);
+            }
+            Commands::New(new_args) => {
+                scaffold::create_new_project(&config, new_args);
+            }
+            Commands::Archive(archive_args) => match &archive_args.command {
+                Some(ArchiveCommands::List(_)) => {
+                    archive::list_archived_projects(&config);
+                }
+                None => {
+                    archive::archive_project(&config, archive_args);
+                }
+            },
+            Commands::Unarchive(unarchive_args) => {
+                archive::unarchive_project(&config, unarchive_args);
+            }
+            Commands::Rm(rm_args) => {
+                trash::remove_project(&config, rm_args);
+            }
+            Commands::Mv(mv_args) => {
+                trash::rename_project(&config, mv_args);
+            }
+            Commands::Undo(_) => {
+                trash::undo_last_operation();
+            }
+            Commands::Clean(clean_args) => {
+                clean::clean_projects(&config, clean_args);
+            }
+            Commands::Stats(stats_args) => {
+                stats::show_stats(&config, stats_args);
+            }
+            Commands::Grep(grep_args) => {
+                grep::grep_projects(&config, grep_args);
+            }
             Commands::Repo(repo) => match &repo.command {
                 RepositoryCommands::Sync(_) => {
                     repositories::sync_version_repositories();
@@ -219,6 +494,6 @@ fn main() {
             },
         }
     } else {
-        projects::get_info_for_project_in_directory(None);
+        info::show_info(&config, &InfoArgs::default());
     }
 }
@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{self, UserConfigSchema};
+use crate::{project_config, projects, resolve};
+
+#[derive(Deserialize, Serialize, Default)]
+pub struct MetadataSchema {
+    #[serde(default)]
+    pub projects: BTreeMap<String, ProjectMetadataSchema>,
+}
+
+#[derive(Deserialize, Serialize, Default, Clone)]
+pub struct ProjectMetadataSchema {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Args)]
+pub struct Tag {
+    #[command(subcommand)]
+    pub command: TagCommands,
+}
+
+#[derive(Subcommand)]
+pub enum TagCommands {
+    /// Add tags to a project
+    Add(TagAddArgs),
+    /// Remove tags from a project
+    Remove(TagRemoveArgs),
+    /// List the tags of a project or all tags
+    List(TagListArgs),
+}
+
+#[derive(Args)]
+pub struct TagAddArgs {
+    /// Name of the project
+    pub project: String,
+    /// Tags to add
+    #[clap(required = true)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Args)]
+pub struct TagRemoveArgs {
+    /// Name of the project
+    pub project: String,
+    /// Tags to remove
+    #[clap(required = true)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Args)]
+pub struct TagListArgs {
+    /// Name of the project
+    pub project: Option<String>,
+}
+
+pub fn get_metadata_path() -> PathBuf {
+    let mut metadata_path = config::get_config_directory();
+    metadata_path.push("projects.toml");
+
+    metadata_path
+}
+
+pub fn read_metadata() -> MetadataSchema {
+    let metadata_path = get_metadata_path();
+
+    if !metadata_path.exists() {
+        return MetadataSchema::default();
+    }
+
+    let metadata_content = std::fs::read_to_string(metadata_path).unwrap_or_else(|_| {
+        error!("Unable to read project metadata file");
+        std::process::exit(1)
+    });
+
+    toml::from_str(&metadata_content).unwrap_or_else(|_| {
+        error!("Invalid project metadata file");
+        std::process::exit(1)
+    })
+}
+
+pub fn write_metadata(metadata: &MetadataSchema) {
+    let metadata_content = toml::to_string(metadata).unwrap_or_else(|_| {
+        error!("Unable to convert project metadata to TOML");
+        std::process::exit(1)
+    });
+
+    std::fs::write(get_metadata_path(), metadata_content).unwrap_or_else(|_| {
+        error!("Unable to write project metadata file");
+        std::process::exit(1)
+    });
+}
+
+pub fn get_project_metadata(project_name: &str) -> ProjectMetadataSchema {
+    read_metadata()
+        .projects
+        .get(project_name)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Move the tags and description of a project to its new name
+pub fn rename_project_metadata(old_project_name: &str, new_project_name: &str) {
+    let mut metadata = read_metadata();
+
+    if let Some(project_metadata) = metadata.projects.remove(old_project_name) {
+        metadata
+            .projects
+            .insert(new_project_name.to_string(), project_metadata);
+        write_metadata(&metadata);
+    }
+}
+
+/// Forget the tags and description of a project and return them
+pub fn remove_project_metadata(project_name: &str) -> Option<ProjectMetadataSchema> {
+    let mut metadata = read_metadata();
+    let project_metadata = metadata.projects.remove(project_name);
+
+    if project_metadata.is_some() {
+        write_metadata(&metadata);
+    }
+
+    project_metadata
+}
+
+pub fn restore_project_metadata(project_name: &str, project_metadata: ProjectMetadataSchema) {
+    let mut metadata = read_metadata();
+
+    metadata
+        .projects
+        .insert(project_name.to_string(), project_metadata);
+    write_metadata(&metadata);
+}
+
+/// Tags from the metadata store combined with the tags in the project's .p.toml
+pub fn get_project_tags(project_name: &str, project_root: &Path) -> Vec<String> {
+    let mut project_tags = get_project_metadata(project_name).tags;
+
+    // An invalid project config is reported where the project is loaded
+    if let Some(project_config_tags) = project_config::try_read_project_config(project_root)
+        .ok()
+        .flatten()
+        .and_then(|project_config| project_config.tags)
+    {
+        for tag in project_config_tags {
+            if !project_tags.contains(&tag) {
+                project_tags.push(tag);
+            }
+        }
+    }
+
+    project_tags
+}
+
+/// Whether a project has all of the required tags
+pub fn has_tags(project_tags: &[String], required_tags: &[String]) -> bool {
+    required_tags
+        .iter()
+        .all(|required_tag| project_tags.contains(required_tag))
+}
+
+pub fn add_tags_to_project(config: &UserConfigSchema, tag_add_args: &TagAddArgs) {
+    let (project_name, _) = resolve::resolve_project(config, &tag_add_args.project);
+    let mut metadata = read_metadata();
+    let project_metadata = metadata.projects.entry(project_name).or_default();
+
+    for tag in &tag_add_args.tags {
+        if !project_metadata.tags.contains(tag) {
+            project_metadata.tags.push(tag.clone());
+        }
+    }
+
+    write_metadata(&metadata);
+}
+
+pub fn remove_tags_from_project(config: &UserConfigSchema, tag_remove_args: &TagRemoveArgs) {
+    let mut metadata = read_metadata();
+    let tagged_project_names: Vec<String> = metadata.projects.keys().cloned().collect();
+
+    if tagged_project_names.is_empty() {
+        return;
+    }
+
+    let project_name =
+        resolve::resolve_project_name(config, &tag_remove_args.project, &tagged_project_names);
+
+    if let Some(project_metadata) = metadata.projects.get_mut(&project_name) {
+        project_metadata
+            .tags
+            .retain(|tag| !tag_remove_args.tags.contains(tag));
+    }
+
+    write_metadata(&metadata);
+}
+
+pub fn list_tags(config: &UserConfigSchema, tag_list_args: &TagListArgs) {
+    if let Some(project_query) = &tag_list_args.project {
+        let (project_name, project_root) = resolve::resolve_project(config, project_query);
+
+        for tag in get_project_tags(&project_name, &project_root) {
+            println!("{}", tag);
+        }
+
+        return;
+    }
+
+    let mut projects_by_tag: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for (project_name, project_root) in projects::get_project_roots(config) {
+        for tag in get_project_tags(&project_name, &project_root) {
+            projects_by_tag
+                .entry(tag)
+                .or_default()
+                .push(project_name.clone());
+        }
+    }
+
+    for (tag, project_names) in projects_by_tag {
+        println!("{} ({})", tag.bold(), project_names.join(", "));
+    }
+}
+
+pub fn describe_project(config: &UserConfigSchema, project_query: &str, description: &str) {
+    let (project_name, _) = resolve::resolve_project(config, project_query);
+    let mut metadata = read_metadata();
+
+    metadata
+        .projects
+        .entry(project_name)
+        .or_default()
+        .description = Some(description.to_string());
+
+    write_metadata(&metadata);
+}
@@ -0,0 +1,36 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::versions::TaskConfigSchema;
+
+/// Name of the optional config file in a project root
+pub const PROJECT_CONFIG_FILE: &str = ".p.toml";
+
+#[derive(Deserialize, Serialize, Default)]
+pub struct ProjectConfigSchema {
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub tasks: Option<BTreeMap<String, TaskConfigSchema>>,
+    pub env: Option<BTreeMap<String, String>>,
+    pub editor: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+/// Read the .p.toml of a project, if it has one. The error is returned so callers going through
+/// all projects can skip a broken one.
+pub fn try_read_project_config(project_root: &Path) -> Result<Option<ProjectConfigSchema>, String> {
+    let project_config_path = project_root.join(PROJECT_CONFIG_FILE);
+
+    if !project_config_path.exists() {
+        return Ok(None);
+    }
+
+    let project_config_content = std::fs::read_to_string(&project_config_path)
+        .map_err(|_| format!("Unable to read {}", project_config_path.display()))?;
+    let project_config_parsed: ProjectConfigSchema = toml::from_str(&project_config_content)
+        .map_err(|e| format!("Invalid {}: {}", project_config_path.display(), e))?;
+
+    Ok(Some(project_config_parsed))
+}
@@ -1,23 +1,70 @@
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 use clap::CommandFactory;
 use clap_complete::generate;
 use clap_complete::Shell::{Bash, Elvish, Fish, PowerShell, Zsh};
 use colored::Colorize;
 use log::error;
-use simsearch::SimSearch;
 
-use crate::versions::VersionConfigSchema;
-use crate::{config, FindArgs, Shell};
-use crate::{config::UserConfigSchema, versions, Cli, CompletionsArgs, ExecuteArgs, GoArgs};
+use crate::fuzzy::{self, CaseSensitivity, FuzzyMatch, MatchOn};
+use crate::metadata::{self, ProjectMetadataSchema};
+use crate::project_config::{self, ProjectConfigSchema, PROJECT_CONFIG_FILE};
+use crate::versions::{TaskConfigSchema, VersionConfigSchema};
+use crate::{
+    aliases, archive, config, environment, frecency, history, index, registry, resolve, tasks,
+    watch, EachArgs, FindArgs,
+};
+use crate::{config::UserConfigSchema, versions, Cli, CompletionsArgs, ExecuteArgs};
+use crate::{ListArgs, Shell};
 
 pub struct Project {
-    versions: Vec<VersionConfigSchema>,
-    root: PathBuf,
-    name: String,
+    pub versions: Vec<VersionConfigSchema>,
+    pub root: PathBuf,
+    pub name: String,
+    pub config: Option<ProjectConfigSchema>,
+    pub metadata: ProjectMetadataSchema,
+}
+
+impl Project {
+    /// Tasks of the most specific version, extended and overridden by the project config
+    pub fn get_tasks(&self) -> BTreeMap<String, TaskConfigSchema> {
+        let mut project_tasks = self.versions[0].tasks.clone().unwrap_or_default();
+
+        if let Some(project_config_tasks) = self
+            .config
+            .as_ref()
+            .and_then(|project_config| project_config.tasks.as_ref())
+        {
+            project_tasks.extend(project_config_tasks.clone());
+        }
+
+        project_tasks
+    }
+
+    /// The description from the metadata store, or else the one in the project config
+    pub fn get_description(&self) -> Option<String> {
+        self.metadata.description.clone().or_else(|| {
+            self.config
+                .as_ref()
+                .and_then(|project_config| project_config.description.clone())
+        })
+    }
 }
 
 pub fn get_project_for_directory(custom_directory: Option<&str>) -> Option<Project> {
+    try_get_project_for_directory(custom_directory).unwrap_or_else(|e| {
+        error!("{}", e);
+        std::process::exit(1)
+    })
+}
+
+/// Like `get_project_for_directory`, but returns the error of an invalid project config instead
+/// of exiting
+pub fn try_get_project_for_directory(
+    custom_directory: Option<&str>,
+) -> Result<Option<Project>, String> {
     let user_config = config::read_config();
     let projects_directory =
         std::path::PathBuf::from(shellexpand::tilde(&user_config.projects_dir).into_owned());
@@ -26,77 +73,105 @@ pub fn get_project_for_directory(custom_directory: Option<&str>) -> Option<Proje
         None => std::env::current_dir().unwrap(),
     };
 
-    if !current_directory.starts_with(&projects_directory) {
-        return None;
-    }
-
-    let project_directory = current_directory
+    let projects_directory_project = current_directory
         .strip_prefix(&projects_directory)
-        .unwrap()
-        .components()
-        .next()
-        .expect("Could not get a project in the given directory")
-        .as_os_str()
-        .to_str()
-        .unwrap();
-
-    Some(Project {
-        versions: versions::get_directory_versions(&projects_directory.join(project_directory)),
-        root: projects_directory.join(project_directory),
-        name: project_directory.to_string(),
-    })
-}
+        .ok()
+        .and_then(|relative_directory| relative_directory.components().next())
+        .map(|project_directory| {
+            let project_directory = project_directory.as_os_str().to_string_lossy().into_owned();
+
+            (
+                project_directory.clone(),
+                projects_directory.join(project_directory),
+            )
+        });
+    let registered_project =
+        registry::get_registered_project_for_directory(&user_config, &current_directory);
+
+    // The deeper root wins, so neither a registered ancestor of the projects directory (e.g. a
+    // dotfiles repository in the home directory) nor the host directory of a nested clone takes
+    // over the projects inside it
+    let (project_name, project_root) = match (registered_project, projects_directory_project) {
+        (Some(registered_project), Some(projects_directory_project)) => {
+            if registered_project.1.components().count()
+                >= projects_directory_project.1.components().count()
+            {
+                registered_project
+            } else {
+                projects_directory_project
+            }
+        }
+        (Some(project), None) | (None, Some(project)) => project,
+        (None, None) => return Ok(None),
+    };
+    let project_config = project_config::try_read_project_config(&project_root)?;
+
+    // A version pinned in the project config skips the detection
+    let project_versions = match project_config
+        .as_ref()
+        .and_then(|project_config| project_config.version.as_ref())
+    {
+        Some(version_name) => {
+            vec![versions::get_version_by_name(version_name).ok_or_else(|| {
+                format!(
+                    "Unknown version {} in {}",
+                    version_name, PROJECT_CONFIG_FILE
+                )
+            })?]
+        }
+        None => versions::get_directory_versions(&project_root),
+    };
+    let project = Project {
+        versions: project_versions,
+        root: project_root,
+        metadata: metadata::get_project_metadata(&project_name),
+        name: project_name,
+        config: project_config,
+    };
 
-pub fn get_info_for_project_in_directory(directory: Option<&str>) {
-    let current_project = get_project_for_directory(directory).unwrap_or_else(|| {
-        error!("Could not find a project in the current directory");
+    tasks::validate_tasks(&project.get_tasks())
+        .map_err(|e| format!("Invalid tasks in {}: {}", PROJECT_CONFIG_FILE, e))?;
 
-        std::process::exit(1);
-    });
+    Ok(Some(project))
+}
 
-    println!(
-        "{}",
-        format!(
-            "Project: {}",
-            std::fs::canonicalize(".")
-                .unwrap()
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-        )
-        .bold()
-        .underline()
-    );
-    if current_project.versions.len() > 1 {
-        println!(
-            "{}",
-            format!("{} Versions:", current_project.versions.len()).bold()
-        );
-        for version in current_project.versions {
-            println!("{} - {}", version.version, version.description);
-        }
-    } else {
-        println!(
-            "{}",
-            format!("Version: {}", current_project.versions[0].version).bold()
-        );
-        println!("{}", current_project.versions[0].description);
-    }
+/// Load a project for a command that goes through all projects. A project with an invalid config
+/// is reported and skipped, so one broken .p.toml doesn't break the listing of the others.
+pub fn get_project_or_skip(project_root: &Path) -> Option<Project> {
+    try_get_project_for_directory(project_root.to_str()).unwrap_or_else(|e| {
+        error!("Skipping {}: {}", project_root.display(), e);
+        None
+    })
 }
 
-pub fn list_projects_in_projects_directory(config: &UserConfigSchema) {
-    let projects_dir = shellexpand::tilde(&config.projects_dir).into_owned();
-    let projects = std::fs::read_dir(&projects_dir).unwrap_or_else(|_| {
-        error!("Unable to read projects directory: {}", projects_dir);
-        std::process::exit(1);
-    });
-    let projects_count = std::fs::read_dir(&projects_dir.clone())
-        .unwrap_or_else(|_| {
-            error!("Unable to read projects directory");
-            std::process::exit(1);
+pub fn list_projects_in_projects_directory(config: &UserConfigSchema, list_args: &ListArgs) {
+    let mut projects: Vec<Project> = get_project_roots(config)
+        .into_iter()
+        .filter(|(project_name, project_root)| {
+            metadata::has_tags(
+                &metadata::get_project_tags(project_name, project_root),
+                &list_args.tag,
+            )
         })
-        .count();
+        .filter_map(|(_, project_root)| get_project_or_skip(&project_root))
+        .collect();
+
+    projects.sort_by_key(|project| !aliases::is_pinned(config, &project.name));
+
+    let archived_projects: Vec<archive::ArchiveManifestSchema> = if list_args.archived {
+        archive::get_archived_projects(config)
+            .into_iter()
+            .filter(|archived_project| {
+                metadata::has_tags(
+                    &metadata::get_project_metadata(&archived_project.name).tags,
+                    &list_args.tag,
+                )
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+    let projects_count = projects.len() + archived_projects.len();
     let projects_string = format!(
         "{} {}:",
         projects_count,
@@ -110,43 +185,166 @@ pub fn list_projects_in_projects_directory(config: &UserConfigSchema) {
     println!("{}", projects_string.bold().underline());
     println!();
 
-    for projects_subdirectory in projects {
-        let projects_subdirectory = &projects_subdirectory.unwrap();
-
-        if projects_subdirectory.path().is_dir() {
-            let project =
-                get_project_for_directory(Some(projects_subdirectory.path().to_str().unwrap()))
-                    .unwrap();
-            let project_versions_string = project
-                .versions
-                .iter()
-                .map(|version| version.version.clone())
-                .collect::<Vec<String>>()
-                .join(", ");
-
-            println!("{} ({})", project.name.bold(), project_versions_string);
+    for project in projects {
+        let project_versions_string = project
+            .versions
+            .iter()
+            .map(|version| version.version.clone())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        println!("{} ({})", project.name.bold(), project_versions_string);
+    }
+
+    for archived_project in archived_projects {
+        println!(
+            "{} ({}) archived",
+            archived_project.name.bold(),
+            archived_project.versions.join(", ")
+        );
+    }
+}
+
+/// Run a command in the root of every project that has the given tags
+pub fn run_in_each_project(config: &UserConfigSchema, each_args: &EachArgs) {
+    let mut failed_projects: Vec<String> = vec![];
+
+    for (project_name, project_root) in get_project_roots(config) {
+        if !metadata::has_tags(
+            &metadata::get_project_tags(&project_name, &project_root),
+            &each_args.tag,
+        ) {
+            continue;
+        }
+
+        println!("{}", format!("> {}", project_name).bold());
+
+        let status = std::process::Command::new(&each_args.command[0])
+            .args(&each_args.command[1..])
+            .current_dir(&project_root)
+            .status()
+            .unwrap_or_else(|_| {
+                error!("Unable to run {}", each_args.command[0]);
+                std::process::exit(1)
+            });
+
+        if !status.success() {
+            failed_projects.push(project_name);
         }
     }
+
+    if !failed_projects.is_empty() {
+        error!("Command failed in {}", failed_projects.join(", "));
+        std::process::exit(1);
+    }
 }
 
 pub fn execute_in_current_project(config: &UserConfigSchema, execute_args: &ExecuteArgs) {
-    let project_result = get_project_for_directory(None);
+    let project = match &execute_args.project {
+        Some(project_query) => {
+            let (project_name, project_root) = resolve::resolve_project(config, project_query);
 
-    let project = match project_result {
-        Some(project) => project,
-        None => {
-            error!("Could not find a project in the current directory");
-            return;
+            get_project_for_directory(project_root.to_str()).unwrap_or_else(|| {
+                error!("Could not find project {}", project_name);
+                std::process::exit(1)
+            })
         }
+        None => match get_project_for_directory(None) {
+            Some(project) => project,
+            None => {
+                error!("Could not find a project in the current directory");
+                return;
+            }
+        },
     };
 
     let project_version = &project.versions[0];
-
     let project_management_tool = match &project_version.project_management_tool {
         Some(project_management_tool) => project_management_tool,
         None => &config.project_management_tool,
     };
-    let project_management_tool_path = std::path::Path::new(&project_management_tool);
+    let project_tasks = project.get_tasks();
+    let project_environment = environment::get_project_environment(&project, execute_args);
+    let discovery_tool = config
+        .discover_tool_tasks
+        .unwrap_or(true)
+        .then_some(project_management_tool.as_str());
+
+    if execute_args.explain_env {
+        environment::explain_environment(&project_environment);
+        return;
+    }
+
+    if execute_args.list {
+        let available_tasks = tasks::get_available_tasks(
+            &project.name,
+            &project.root,
+            &project_tasks,
+            discovery_tool,
+        );
+
+        tasks::list_tasks(&available_tasks, execute_args.compact);
+        return;
+    }
+
+    // Without arguments, offer the available tasks instead of calling the tool with nothing
+    let arguments = if execute_args.arguments.is_empty() {
+        let available_tasks = tasks::get_available_tasks(
+            &project.name,
+            &project.root,
+            &project_tasks,
+            discovery_tool,
+        );
+
+        if available_tasks.is_empty() {
+            vec![]
+        } else {
+            match tasks::pick_task(&available_tasks) {
+                Some(task_name) => vec![task_name],
+                None => return,
+            }
+        }
+    } else {
+        execute_args.arguments.clone()
+    };
+
+    if execute_args.watch {
+        watch::watch_project(&project.root, project_version, execute_args, &arguments);
+        return;
+    }
+
+    // Tasks defined by the version or the project take precedence over the project management tool
+    if let Some(task_name) = arguments.first() {
+        if project_tasks.contains_key(task_name) {
+            let history_record = history::HistoryRecord::new(
+                &project.name,
+                &project_version.version,
+                "version task",
+                &arguments,
+            );
+            let started_at = Instant::now();
+            let exit_code = tasks::run_task(
+                &project.root,
+                &project_tasks,
+                task_name,
+                &arguments[1..],
+                &project_environment,
+                false,
+            );
+
+            history_record.finish(started_at.elapsed(), exit_code);
+
+            if exit_code != 0 {
+                std::process::exit(exit_code);
+            }
+
+            return;
+        }
+    }
+
+    let project_management_tool_path = project
+        .root
+        .join(shellexpand::tilde(project_management_tool).into_owned());
 
     if !project_management_tool_path.exists() {
         println!(
@@ -156,11 +354,23 @@ pub fn execute_in_current_project(config: &UserConfigSchema, execute_args: &Exec
         return;
     }
 
-    let mut command = std::process::Command::new(&project_management_tool);
+    let history_record = history::HistoryRecord::new(
+        &project.name,
+        &project_version.version,
+        project_management_tool,
+        &arguments,
+    );
+    let started_at = Instant::now();
+    let mut command = std::process::Command::new(&project_management_tool_path);
 
     command.current_dir(&project.root);
-    command.args(&execute_args.arguments);
-    command
+    command.args(&arguments);
+    command.envs(
+        project_environment
+            .iter()
+            .map(|variable| (&variable.key, &variable.value)),
+    );
+    let exit_status = command
         .spawn()
         .unwrap_or_else(|_| {
             error!("Error executing command in current project");
@@ -171,19 +381,90 @@ pub fn execute_in_current_project(config: &UserConfigSchema, execute_args: &Exec
             error!("Error executing command in current project");
             std::process::exit(1)
         });
+    let exit_code = exit_status.code().unwrap_or(1);
+
+    history_record.finish(started_at.elapsed(), exit_code);
+
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
 }
 
-pub fn get_project_path(config: &UserConfigSchema, go_args: &GoArgs) -> Option<String> {
-    let mut project_path_string = shellexpand::tilde(&config.projects_dir).into_owned();
-    project_path_string.push_str("/");
-    project_path_string.push_str(&go_args.project);
+/// All directories in the projects directory and all registered projects with their project
+/// names, sorted by name
+pub fn get_project_roots(config: &UserConfigSchema) -> Vec<(String, PathBuf)> {
+    let projects_dir = shellexpand::tilde(&config.projects_dir).into_owned();
+    let projects = std::fs::read_dir(&projects_dir).unwrap_or_else(|_| {
+        error!("Unable to read projects directory: {}", projects_dir);
+        std::process::exit(1);
+    });
+    let registered_projects: Vec<(String, PathBuf)> = registry::get_registered_projects(config)
+        .into_iter()
+        .filter(|(_, project_root)| project_root.is_dir())
+        .collect();
+    let mut project_roots: Vec<(String, PathBuf)> = projects
+        .map(|project| {
+            project.unwrap_or_else(|_| {
+                error!("Unable to read project");
+                std::process::exit(1)
+            })
+        })
+        .filter(|project| project.path().is_dir())
+        // Directories like github.com/ that only hold registered projects, e.g. from nested
+        // clones, are not projects themselves
+        .filter(|project| {
+            !registered_projects.iter().any(|(_, registered_root)| {
+                registered_root != &project.path() && registered_root.starts_with(project.path())
+            })
+        })
+        .map(|project| {
+            (
+                project.file_name().to_string_lossy().into_owned(),
+                project.path(),
+            )
+        })
+        .collect();
+
+    project_roots.extend(registered_projects);
+    project_roots.sort();
+    project_roots
+}
 
-    let project_path = std::path::Path::new(&project_path_string);
-    if !project_path.exists() {
+/// Whether the project lives inside the projects directory, unlike most registered projects
+pub fn is_in_projects_directory(config: &UserConfigSchema, project_root: &Path) -> bool {
+    let projects_directory = PathBuf::from(shellexpand::tilde(&config.projects_dir).into_owned());
+
+    project_root.starts_with(&projects_directory)
+        || std::fs::canonicalize(&projects_directory)
+            .is_ok_and(|projects_directory| project_root.starts_with(projects_directory))
+}
+
+/// Whether a name can be used for a project: it stays inside the projects directory when joined
+/// onto it and doesn't create a hidden directory. Leading dots also rule out "." and "..".
+pub fn is_valid_project_name(project_name: &str) -> bool {
+    !project_name.is_empty()
+        && !project_name.starts_with('.')
+        && !project_name.contains(['/', '\\'])
+}
+
+pub fn get_project_root(config: &UserConfigSchema, project_name: &str) -> Option<PathBuf> {
+    if !is_valid_project_name(project_name) {
         return None;
     }
 
-    return Some(project_path.to_str().unwrap().to_owned());
+    let project_root =
+        PathBuf::from(shellexpand::tilde(&config.projects_dir).into_owned()).join(project_name);
+
+    if project_root.is_dir() {
+        return Some(project_root);
+    }
+
+    registry::get_registered_projects(config)
+        .into_iter()
+        .find(|(registered_name, project_root)| {
+            registered_name == project_name && project_root.is_dir()
+        })
+        .map(|(_, project_root)| project_root)
 }
 
 pub fn get_shell_completions(completions_args: &CompletionsArgs) {
@@ -209,51 +490,173 @@ pub fn get_shell_completions(completions_args: &CompletionsArgs) {
     }
 }
 
-pub fn find_project_in_projects_directory(config: &UserConfigSchema, command_config: &FindArgs) {
-    let projects_dir = shellexpand::tilde(&config.projects_dir).into_owned();
-    let projects = std::fs::read_dir(&projects_dir).unwrap_or_else(|_| {
-        error!("Unable to read projects directory: {}", projects_dir);
-        std::process::exit(1);
+/// How much visiting a project often and recently weighs against how well its name matches
+const FRECENCY_WEIGHT: f64 = 0.5;
+
+/// Rank the given project names by how well they match the query, with frequently and recently
+/// visited projects moving up. Projects that don't match are left out.
+pub fn rank_project_names(
+    config: &UserConfigSchema,
+    query: &str,
+    project_names: &[String],
+) -> Vec<String> {
+    let candidates: Vec<(String, String)> = project_names
+        .iter()
+        .map(|project_name| (project_name.clone(), project_name.clone()))
+        .collect();
+
+    rank_projects(config, query, &candidates, CaseSensitivity::default())
+        .into_iter()
+        .map(|(project_name, _)| project_name)
+        .collect()
+}
+
+/// Rank projects by how well the query matches their candidate text, which is the name or the
+/// path. Returns the project names with the positions of the matched characters.
+fn rank_projects(
+    config: &UserConfigSchema,
+    query: &str,
+    candidates: &[(String, String)],
+    case_sensitivity: CaseSensitivity,
+) -> Vec<(String, Vec<usize>)> {
+    let mut fuzzy_matches: Vec<(usize, FuzzyMatch)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(candidate_index, (_, candidate))| {
+            fuzzy::fuzzy_match(query, candidate, case_sensitivity)
+                .map(|fuzzy_match| (candidate_index, fuzzy_match))
+        })
+        .collect();
+
+    if fuzzy_matches.is_empty() {
+        fuzzy_matches = candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(candidate_index, (_, candidate))| {
+                fuzzy::typo_tolerant_match(query, candidate, case_sensitivity)
+                    .map(|fuzzy_match| (candidate_index, fuzzy_match))
+            })
+            .collect();
+    }
+
+    let frecency_scores = frecency::get_frecency_scores();
+    let max_frecency_score = frecency_scores.values().cloned().fold(1.0, f64::max);
+    let max_match_score = fuzzy_matches
+        .iter()
+        .map(|(_, fuzzy_match)| fuzzy_match.score)
+        .max()
+        .unwrap_or(1)
+        .max(1) as f64;
+    let mut ranked_projects: Vec<(f64, usize, FuzzyMatch)> = fuzzy_matches
+        .into_iter()
+        .map(|(candidate_index, fuzzy_match)| {
+            let project_name = &candidates[candidate_index].0;
+            let relevance = if project_name == query {
+                2.0
+            } else {
+                fuzzy_match.score.max(0) as f64 / max_match_score
+            };
+            let frecency = frecency_scores.get(project_name).unwrap_or(&0.0) / max_frecency_score;
+
+            (
+                relevance + frecency * FRECENCY_WEIGHT,
+                candidate_index,
+                fuzzy_match,
+            )
+        })
+        .collect();
+
+    // Shorter candidates win ties, "api" should rank "api" above "api-gateway"
+    ranked_projects.sort_by(|a, b| {
+        b.0.total_cmp(&a.0)
+            .then(candidates[a.1].1.len().cmp(&candidates[b.1].1.len()))
     });
-    let mut engine: SimSearch<u32> = SimSearch::new();
-    let mut engine_insert_index = 0;
-    let mut project_names: Vec<String> = Vec::new();
+
+    let mut ranked_projects: Vec<(String, Vec<usize>)> = ranked_projects
+        .into_iter()
+        .map(|(_, candidate_index, fuzzy_match)| {
+            (candidates[candidate_index].0.clone(), fuzzy_match.positions)
+        })
+        .collect();
+
+    // An alias always finds the project it points to first
+    let aliased_project_name = aliases::resolve_alias(config, query);
+
+    if aliased_project_name != query
+        && candidates
+            .iter()
+            .any(|(project_name, _)| project_name == &aliased_project_name)
+    {
+        ranked_projects.retain(|(project_name, _)| project_name != &aliased_project_name);
+        ranked_projects.insert(0, (aliased_project_name, vec![]));
+    }
+
+    ranked_projects
+}
+
+pub fn find_project_in_projects_directory(config: &UserConfigSchema, command_config: &FindArgs) {
+    if command_config.content {
+        index::find_projects_by_content(config, command_config);
+        return;
+    }
+
     let project_name = &command_config.project.to_owned();
     let compact = &command_config.compact.to_owned();
     let amount = match &command_config.amount {
         Some(amount) => amount.to_owned(),
         None => 5,
     };
-
-    for project in projects {
-        let project = project.unwrap_or_else(|_| {
-            error!("Unable to read project");
-            std::process::exit(1)
-        });
-        let project_path = project.path();
-
-        project_names.push(
-            project_path
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_string(),
-        );
-    }
-
-    for project_name in &project_names {
-        engine.insert(engine_insert_index, &project_name);
-        engine_insert_index += 1;
-    }
-
-    let project_search_result = engine.search(project_name);
-
-    if project_search_result.len() == 0 {
+    let archived_projects = if command_config.archived {
+        archive::get_archived_projects(config)
+    } else {
+        vec![]
+    };
+    let archived_project_names: Vec<String> = archived_projects
+        .iter()
+        .map(|archived_project| archived_project.name.clone())
+        .collect();
+    let candidates: Vec<(String, String)> = get_project_roots(config)
+        .into_iter()
+        .map(|(project_name, project_root)| (project_name, project_root.display().to_string()))
+        .chain(
+            archived_projects
+                .into_iter()
+                .map(|archived_project| (archived_project.name, archived_project.original_path)),
+        )
+        .map(
+            |(project_name, project_path)| match command_config.match_on {
+                MatchOn::Name => (project_name.clone(), project_name),
+                MatchOn::Path => (project_name, project_path),
+            },
+        )
+        .collect();
+
+    let mut project_search_result: Vec<(String, Vec<usize>)> =
+        rank_projects(config, project_name, &candidates, command_config.case)
+            .into_iter()
+            .filter(
+                |(project_name, _)| match get_project_root(config, project_name) {
+                    Some(project_root) => metadata::has_tags(
+                        &metadata::get_project_tags(project_name, &project_root),
+                        &command_config.tag,
+                    ),
+                    // Archived projects only have the tags from the metadata store
+                    None => metadata::has_tags(
+                        &metadata::get_project_metadata(project_name).tags,
+                        &command_config.tag,
+                    ),
+                },
+            )
+            .collect();
+
+    if project_search_result.is_empty() {
         error!("No project \"{}\" found", project_name);
         return;
     }
 
+    project_search_result
+        .sort_by_key(|(project_name, _)| !aliases::is_pinned(config, project_name));
+
     if !compact {
         println!(
             "{}",
@@ -263,62 +666,27 @@ pub fn find_project_in_projects_directory(config: &UserConfigSchema, command_con
         );
     }
 
-    let top_project_search_results = project_search_result
-        .iter()
-        .take(amount)
-        .collect::<Vec<&u32>>();
-
-    for project_search_result_index in top_project_search_results {
-        let project_at_index = &project_names[*project_search_result_index as usize];
-
-        println!("{}", project_at_index);
-    }
-}
-
-pub fn open_editor_in_current_project(
-    config: &UserConfigSchema,
-    editor: &Option<String>,
-    detach: bool,
-) {
-    let editor = match editor.to_owned() {
-        Some(editor) => Some(editor),
-        None => config.editor.clone(),
-    };
-
-    if editor.clone().is_none() {
-        println!(
-            "No editor set. Please set your preferred code editor or IDE in your config file. Or specify an editor with the --editor flag."
-        );
-
-        return;
-    }
-
-    // If the detach flag is set, run the editor in the background
-    if detach {
-        std::process::Command::new("sh")
-            .arg("-c")
-            .arg(editor.unwrap())
-            .spawn()
-            .unwrap_or_else(|_| {
-                error!("Failed to run editor");
-                std::process::exit(1)
-            });
+    for (project_at_index, positions) in project_search_result.iter().take(amount) {
+        if *compact {
+            println!("{}", project_at_index);
+            continue;
+        }
 
-        return;
+        let candidate = candidates
+            .iter()
+            .find(|(project_name, _)| project_name == project_at_index)
+            .map(|(_, candidate)| candidate.as_str())
+            .unwrap_or(project_at_index);
+        let highlighted_candidate = fuzzy::highlight_positions(candidate, positions);
+        let project_line = match command_config.match_on {
+            MatchOn::Name => highlighted_candidate,
+            MatchOn::Path => format!("{} {}", project_at_index, highlighted_candidate),
+        };
+
+        if archived_project_names.contains(project_at_index) {
+            println!("{} (archived)", project_line);
+        } else {
+            println!("{}", project_line);
+        }
     }
-
-    // Otherwise, run the editor in the foreground
-    std::process::Command::new("sh")
-        .arg("-c")
-        .arg(editor.unwrap())
-        .spawn()
-        .unwrap_or_else(|_| {
-            error!("Failed to run editor");
-            std::process::exit(1)
-        })
-        .wait()
-        .unwrap_or_else(|_| {
-            error!("Editor returned a non-zero status");
-            std::process::exit(1)
-        });
 }
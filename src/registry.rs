@@ -0,0 +1,180 @@
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use log::error;
+
+use crate::config::{self, UserConfigSchema};
+use crate::{projects, resolve};
+
+#[derive(Args)]
+pub struct RegisterArgs {
+    /// Path of the project
+    pub path: String,
+
+    /// Name of the project, defaults to the name of the directory
+    #[clap(short, long)]
+    pub name: Option<String>,
+}
+
+#[derive(Args)]
+pub struct UnregisterArgs {
+    /// Name or path of the project, defaults to the project in the current directory
+    pub project: Option<String>,
+}
+
+/// Projects registered outside of the projects directory with their roots, sorted by name
+pub fn get_registered_projects(config: &UserConfigSchema) -> Vec<(String, PathBuf)> {
+    let mut registered_projects: Vec<(String, PathBuf)> = config
+        .registered_projects
+        .iter()
+        .flat_map(|registered_projects| registered_projects.iter())
+        .map(|(project_name, project_path)| {
+            (
+                project_name.clone(),
+                PathBuf::from(shellexpand::tilde(project_path).into_owned()),
+            )
+        })
+        .collect();
+
+    registered_projects.sort();
+    registered_projects
+}
+
+/// The registered project containing the directory. Nested registrations resolve to the
+/// innermost project.
+pub fn get_registered_project_for_directory(
+    config: &UserConfigSchema,
+    directory: &Path,
+) -> Option<(String, PathBuf)> {
+    get_registered_projects(config)
+        .into_iter()
+        .filter(|(_, project_root)| directory.starts_with(project_root))
+        .max_by_key(|(_, project_root)| project_root.components().count())
+}
+
+pub fn is_registered(config: &UserConfigSchema, project_name: &str) -> bool {
+    config
+        .registered_projects
+        .as_ref()
+        .is_some_and(|registered_projects| registered_projects.contains_key(project_name))
+}
+
+/// Register a project at the given root, or remove its registration
+pub fn set_registered_project_root(project_name: &str, project_root: Option<&Path>) {
+    let mut config = config::read_config();
+    let registered_projects = config
+        .registered_projects
+        .get_or_insert_with(Default::default);
+
+    match project_root {
+        Some(project_root) => registered_projects.insert(
+            project_name.to_string(),
+            project_root.to_string_lossy().into_owned(),
+        ),
+        None => registered_projects.remove(project_name),
+    };
+
+    config::write_config(config);
+}
+
+pub fn register_project(register_args: &RegisterArgs) {
+    let mut config = config::read_config();
+    let project_root = std::fs::canonicalize(shellexpand::tilde(&register_args.path).as_ref())
+        .unwrap_or_else(|_| {
+            error!("Directory {} does not exist", register_args.path);
+            std::process::exit(1)
+        });
+
+    if !project_root.is_dir() {
+        error!("{} is not a directory", project_root.display());
+        std::process::exit(1)
+    }
+
+    if let Some((registered_name, _)) = get_registered_projects(&config)
+        .into_iter()
+        .find(|(_, registered_root)| registered_root == &project_root)
+    {
+        error!(
+            "{} is already registered as {}",
+            project_root.display(),
+            registered_name
+        );
+        std::process::exit(1)
+    }
+
+    let project_name = match &register_args.name {
+        Some(project_name) => project_name.clone(),
+        None => project_root
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| {
+                error!("Please specify a name for {}", project_root.display());
+                std::process::exit(1)
+            }),
+    };
+
+    if !projects::is_valid_project_name(&project_name) {
+        error!(
+            "{} is not a valid project name. Use --name to choose another name.",
+            project_name
+        );
+        std::process::exit(1)
+    }
+
+    if projects::get_project_root(&config, &project_name).is_some() {
+        error!(
+            "A project called {} already exists. Use --name to choose another name.",
+            project_name
+        );
+        std::process::exit(1)
+    }
+
+    config
+        .registered_projects
+        .get_or_insert_with(Default::default)
+        .insert(project_name, project_root.to_string_lossy().into_owned());
+
+    config::write_config(config);
+}
+
+pub fn unregister_project(unregister_args: &UnregisterArgs) {
+    let mut config = config::read_config();
+    let project_name = match &unregister_args.project {
+        Some(project)
+            if config
+                .registered_projects
+                .as_ref()
+                .is_some_and(|registered_projects| registered_projects.contains_key(project)) =>
+        {
+            Some(project.clone())
+        }
+        Some(project) => std::fs::canonicalize(shellexpand::tilde(project).as_ref())
+            .ok()
+            .and_then(|directory| get_registered_project_for_directory(&config, &directory))
+            .map(|(project_name, _)| project_name)
+            .or_else(|| {
+                let registered_project_names: Vec<String> = get_registered_projects(&config)
+                    .into_iter()
+                    .map(|(project_name, _)| project_name)
+                    .collect();
+
+                (!registered_project_names.is_empty()).then(|| {
+                    resolve::resolve_project_name(&config, project, &registered_project_names)
+                })
+            }),
+        None => std::env::current_dir()
+            .ok()
+            .and_then(|directory| get_registered_project_for_directory(&config, &directory))
+            .map(|(project_name, _)| project_name),
+    };
+    let project_name = project_name.unwrap_or_else(|| {
+        error!("No registered project found");
+        std::process::exit(1)
+    });
+
+    if let Some(registered_projects) = &mut config.registered_projects {
+        registered_projects.remove(&project_name);
+    }
+
+    config::write_config(config);
+}
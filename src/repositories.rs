@@ -307,6 +307,10 @@ pub fn create_new_repository(repository_name: &str) {
             error!("Unable to create versions directory");
             std::process::exit(1)
         });
+        std::fs::create_dir(version_repository_path.join("templates")).unwrap_or_else(|_| {
+            error!("Unable to create templates directory");
+            std::process::exit(1)
+        });
 
         println!(
             "New external version repository \"{}\" created in {}",
@@ -314,8 +318,8 @@ pub fn create_new_repository(repository_name: &str) {
             &version_repository_path.display()
         );
         println!(
-            "Add version configs to {}/versions, commit and push the results.",
-            repository_name
+            "Add version configs to {}/versions and project templates to {}/templates, commit and push the results.",
+            repository_name, repository_name
         );
         println!("You will then be able to add the repository to your config by running \"p repo add REPOSITORY_URL\"");
     } else {
@@ -0,0 +1,141 @@
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use log::error;
+
+use crate::config::UserConfigSchema;
+use crate::{aliases, projects};
+
+/// How many matches are listed when a project name is ambiguous
+const MAX_LISTED_MATCHES: usize = 10;
+
+/// Resolve what the user typed to one of the given project names. Tries the exact name, an alias,
+/// a unique prefix and finally a fuzzy match. If several projects match, the user can pick one
+/// when p runs in a terminal, otherwise the matches are listed and p exits.
+pub fn resolve_project_name(
+    config: &UserConfigSchema,
+    project_query: &str,
+    project_names: &[String],
+) -> String {
+    if project_names
+        .iter()
+        .any(|project_name| project_name == project_query)
+    {
+        return project_query.to_string();
+    }
+
+    let aliased_project_name = aliases::resolve_alias(config, project_query);
+
+    if project_names.contains(&aliased_project_name) {
+        return aliased_project_name;
+    }
+
+    let lowercase_query = project_query.to_lowercase();
+    let prefix_matches: Vec<String> = project_names
+        .iter()
+        .filter(|project_name| project_name.to_lowercase().starts_with(&lowercase_query))
+        .cloned()
+        .collect();
+    let matches = match prefix_matches.len() {
+        0 => projects::rank_project_names(config, project_query, project_names),
+        1 => return prefix_matches[0].clone(),
+        _ => projects::rank_project_names(config, project_query, &prefix_matches),
+    };
+
+    match matches.len() {
+        0 => {
+            error!("Project {} does not exist", project_query);
+            std::process::exit(1)
+        }
+        1 => matches[0].clone(),
+        _ => pick_project_name(project_query, &matches),
+    }
+}
+
+/// Resolve what the user typed to a project and its root directory
+pub fn resolve_project(config: &UserConfigSchema, project_query: &str) -> (String, PathBuf) {
+    let project_roots = projects::get_project_roots(config);
+    let project_names: Vec<String> = project_roots
+        .iter()
+        .map(|(project_name, _)| project_name.clone())
+        .collect();
+    let project_name = resolve_project_name(config, project_query, &project_names);
+    let project_root = project_roots
+        .into_iter()
+        .find(|(root_project_name, _)| root_project_name == &project_name)
+        .map(|(_, project_root)| project_root)
+        .unwrap_or_else(|| {
+            error!("Project {} does not exist", project_name);
+            std::process::exit(1)
+        });
+
+    (project_name, project_root)
+}
+
+/// Resolve a project for a command that moves or deletes it. Only the exact name or an alias is
+/// accepted, so a typo can never hit another project. Close matches are suggested instead.
+pub fn resolve_exact_project(config: &UserConfigSchema, project_query: &str) -> (String, PathBuf) {
+    let project_roots = projects::get_project_roots(config);
+    let aliased_project_name = aliases::resolve_alias(config, project_query);
+
+    if let Some((project_name, project_root)) = project_roots
+        .iter()
+        .find(|(project_name, _)| project_name == project_query)
+        .or_else(|| {
+            project_roots
+                .iter()
+                .find(|(project_name, _)| project_name == &aliased_project_name)
+        })
+    {
+        return (project_name.clone(), project_root.clone());
+    }
+
+    let project_names: Vec<String> = project_roots
+        .into_iter()
+        .map(|(project_name, _)| project_name)
+        .collect();
+    let suggestions = projects::rank_project_names(config, project_query, &project_names);
+
+    error!("Project {} does not exist", project_query);
+
+    if !suggestions.is_empty() {
+        eprintln!("Did you mean:");
+
+        for project_name in suggestions.iter().take(MAX_LISTED_MATCHES) {
+            eprintln!("  {}", project_name);
+        }
+    }
+
+    std::process::exit(1)
+}
+
+/// Let the user pick one of several matching projects. The prompt is drawn on stderr, so this
+/// also works for `cd $(p go NAME)`.
+fn pick_project_name(project_query: &str, matches: &[String]) -> String {
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        error!("\"{}\" matches several projects:", project_query);
+
+        for project_name in matches.iter().take(MAX_LISTED_MATCHES) {
+            eprintln!("  {}", project_name);
+        }
+
+        if matches.len() > MAX_LISTED_MATCHES {
+            eprintln!("  and {} more", matches.len() - MAX_LISTED_MATCHES);
+        }
+
+        std::process::exit(1)
+    }
+
+    let selected_index = dialoguer::Select::new()
+        .with_prompt(format!("\"{}\" matches several projects", project_query))
+        .items(matches)
+        .default(0)
+        .interact_opt()
+        .unwrap_or_else(|_| {
+            error!("Unable to select a project");
+            std::process::exit(1)
+        })
+        .unwrap_or_else(|| std::process::exit(1));
+
+    matches[selected_index].clone()
+}
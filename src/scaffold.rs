@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use clap::Args;
+use log::error;
+
+use crate::config::{self, UserConfigSchema};
+use crate::versions::{self, VersionConfigSchema};
+use crate::{projects, repositories, shell, tasks};
+
+/// Name of the version task that runs after a project has been created with `p new`
+pub const INIT_TASK: &str = "init";
+
+#[derive(Args)]
+pub struct NewArgs {
+    /// Name of the project
+    pub name: String,
+
+    /// Version of the project
+    #[clap(long)]
+    pub version: String,
+
+    /// Initialize a git repository in the project
+    #[clap(short, long)]
+    pub git: bool,
+
+    /// Do not run the init task of the version
+    #[clap(long)]
+    pub no_init: bool,
+}
+
+/// Find the template of a version in ~/.p/templates/ or in the templates directory of a version
+/// repository. The template directory is named after the version, ignoring case.
+fn get_version_template_directory(version_name: &str) -> Option<PathBuf> {
+    let mut templates_directories = vec![config::get_config_directory().join("templates")];
+
+    if let Ok(repository_directories) =
+        std::fs::read_dir(repositories::get_repositories_directory())
+    {
+        let mut repository_directories: Vec<PathBuf> = repository_directories
+            .flatten()
+            .map(|repository_directory| repository_directory.path().join("templates"))
+            .collect();
+
+        repository_directories.sort();
+        templates_directories.extend(repository_directories);
+    }
+
+    templates_directories
+        .into_iter()
+        .filter_map(|templates_directory| std::fs::read_dir(templates_directory).ok())
+        .flat_map(|template_directories| template_directories.flatten())
+        .find(|template_directory| {
+            template_directory.path().is_dir()
+                && template_directory
+                    .file_name()
+                    .to_string_lossy()
+                    .eq_ignore_ascii_case(version_name)
+        })
+        .map(|template_directory| template_directory.path())
+}
+
+fn render_template_string(template: &str, project_name: &str, version_name: &str) -> String {
+    template
+        .replace("{{name}}", project_name)
+        .replace("{{version}}", version_name)
+}
+
+/// Copy a template directory, filling the placeholders in file names and text files
+fn render_template(
+    template_directory: &Path,
+    project_directory: &Path,
+    project_name: &str,
+    version_name: &str,
+) -> std::io::Result<()> {
+    std::fs::create_dir_all(project_directory)?;
+
+    for entry in std::fs::read_dir(template_directory)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+
+        if file_name == ".git" {
+            continue;
+        }
+
+        let target_path = project_directory.join(render_template_string(
+            &file_name,
+            project_name,
+            version_name,
+        ));
+
+        if entry.file_type()?.is_dir() {
+            render_template(&entry.path(), &target_path, project_name, version_name)?;
+            continue;
+        }
+
+        match std::fs::read_to_string(entry.path()) {
+            Ok(content) => std::fs::write(
+                &target_path,
+                render_template_string(&content, project_name, version_name),
+            )?,
+            Err(_) => {
+                std::fs::copy(entry.path(), &target_path)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_scaffold_command(scaffold: &str, project_root: &Path, project_name: &str) -> bool {
+    let scaffold_command = scaffold
+        .replace("{name}", &shell::quote_argument(project_name))
+        .replace(
+            "{root}",
+            &shell::quote_argument(&project_root.to_string_lossy()),
+        );
+
+    eprintln!("> scaffold: {}", scaffold_command);
+
+    Command::new("sh")
+        .arg("-c")
+        .arg(&scaffold_command)
+        .current_dir(project_root)
+        .stdout(Stdio::from(std::io::stderr()))
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+fn remove_failed_project(project_root: &Path, message: &str) -> ! {
+    error!("{}", message);
+    let _ = std::fs::remove_dir_all(project_root);
+    std::process::exit(1)
+}
+
+pub fn create_new_project(config: &UserConfigSchema, new_args: &NewArgs) {
+    if !projects::is_valid_project_name(&new_args.name) {
+        error!("{} is not a valid project name", new_args.name);
+        std::process::exit(1)
+    }
+
+    let version: VersionConfigSchema = versions::get_version_by_name(&new_args.version)
+        .unwrap_or_else(|| {
+            error!("Unknown version {}", new_args.version);
+            std::process::exit(1)
+        });
+    let project_root =
+        PathBuf::from(shellexpand::tilde(&config.projects_dir).into_owned()).join(&new_args.name);
+
+    if project_root.exists() || projects::get_project_root(config, &new_args.name).is_some() {
+        error!("A project called {} already exists", new_args.name);
+        std::process::exit(1)
+    }
+
+    let template_directory = get_version_template_directory(&version.version);
+
+    if template_directory.is_none() && version.scaffold.is_none() {
+        error!(
+            "Version {} has neither a template nor a scaffold command",
+            version.version
+        );
+        std::process::exit(1)
+    }
+
+    std::fs::create_dir_all(&project_root).unwrap_or_else(|_| {
+        error!("Unable to create {}", project_root.display());
+        std::process::exit(1)
+    });
+
+    // Generators usually want an empty directory, so the scaffold command runs before the template
+    if let Some(scaffold) = &version.scaffold {
+        if !run_scaffold_command(scaffold, &project_root, &new_args.name) {
+            remove_failed_project(&project_root, "Scaffold command failed");
+        }
+    }
+
+    if let Some(template_directory) = &template_directory {
+        if render_template(
+            template_directory,
+            &project_root,
+            &new_args.name,
+            &version.version,
+        )
+        .is_err()
+        {
+            remove_failed_project(&project_root, "Unable to render the template");
+        }
+    }
+
+    if new_args.git {
+        let git_status = Command::new("git")
+            .arg("init")
+            .arg("-q")
+            .current_dir(&project_root)
+            .status();
+
+        if !git_status.is_ok_and(|status| status.success()) {
+            error!("Unable to initialize a git repository");
+        }
+    }
+
+    let version_tasks = version.tasks.clone().unwrap_or_default();
+    let exit_code = if !new_args.no_init && version_tasks.contains_key(INIT_TASK) {
+        tasks::run_task(&project_root, &version_tasks, INIT_TASK, &[], &[], true)
+    } else {
+        0
+    };
+
+    // Keep stdout free for the path of the project, so `cd $(p new NAME --version V)` works
+    println!("{}", project_root.display());
+
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+}
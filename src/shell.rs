@@ -23,6 +23,10 @@ pf() {
 pfg() {
     pg "$(p find -c -a 1 "$@")"
 }
+_px() {
+    COMPREPLY=($(compgen -W "$(p execute --list --compact 2>/dev/null)" -- "${COMP_WORDS[COMP_CWORD]}"))
+}
+complete -F _px px
 "#;
     let zsh_shell_aliases = r#"
 pg() {
@@ -46,6 +50,10 @@ pf() {
 pfg() {
     pg "$(p find -c -a 1 "$@")"
 }
+_px() {
+    compadd -- $(p execute --list --compact 2>/dev/null)
+}
+(( $+functions[compdef] )) && compdef _px px
     "#;
 
     let fish_shell_aliases = r#"
@@ -76,6 +84,8 @@ end
 function pfg
     pg (p find -c -a 1 $argv)
 end
+
+complete -c px -f -a "(p execute --list --compact 2>/dev/null)"
     "#;
 
     let powershell_shell_aliases = r#"
@@ -84,7 +94,8 @@ function pg {
 }
 
 function px {
-    p execute @args
+    param([Parameter(Position = 0, ValueFromRemainingArguments)][string[]]$Arguments)
+    p execute @Arguments
 }
 
 function pl {
@@ -106,6 +117,11 @@ function pf {
 function pfg {
     pg (p find -c -a 1 @args)
 }
+
+Register-ArgumentCompleter -CommandName px -ParameterName Arguments -ScriptBlock {
+    param($commandName, $parameterName, $wordToComplete)
+    p execute --list --compact 2>$null | Where-Object { $_ -like "$wordToComplete*" }
+}
     "#;
 
     let elvish_shell_aliases = r#"
@@ -136,6 +152,10 @@ fn pf {
 fn pfg {
     pg (p find -c -a 1 $args)
 }
+
+set edit:completion:arg-completer[px] = {|@args|
+    try { p execute --list --compact 2>/dev/null } catch { }
+}
     "#;
 
     match alias_args.shell {
@@ -152,3 +172,8 @@ pub fn log_shell_aliases(alias_args: &AliasArgs) {
     println!("{}", get_shell_aliases(&alias_args));
     println!("You can add them to your shell configuration file. (e.g. ~/.bashrc, ~/.zshrc, ...)");
 }
+
+/// Quote an argument so that `sh -c` passes it on unchanged
+pub fn quote_argument(argument: &str) -> String {
+    format!("'{}'", argument.replace('\'', "'\\''"))
+}
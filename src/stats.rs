@@ -0,0 +1,241 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use colored::Colorize;
+use log::error;
+use rayon::prelude::*;
+
+use crate::clean::{self, format_size};
+use crate::config::UserConfigSchema;
+use crate::projects::{self, Project};
+use crate::resolve;
+
+#[derive(Args)]
+pub struct StatsArgs {
+    /// Name or alias of the project, shows all projects if not set
+    pub project: Option<String>,
+}
+
+#[derive(Default)]
+pub struct LanguageStats {
+    pub files: u64,
+    pub lines: u64,
+}
+
+pub struct ProjectStats {
+    pub name: String,
+    pub size: u64,
+    pub artifact_size: u64,
+    pub files: u64,
+    pub languages: BTreeMap<String, LanguageStats>,
+}
+
+impl ProjectStats {
+    pub fn get_lines(&self) -> u64 {
+        self.languages.values().map(|language| language.lines).sum()
+    }
+
+    /// The language with the most lines of code
+    pub fn get_main_language(&self) -> Option<&String> {
+        self.languages
+            .iter()
+            .max_by_key(|(_, language)| language.lines)
+            .map(|(language_name, _)| language_name)
+    }
+}
+
+fn get_language(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let language = match extension.as_str() {
+        "rs" => "Rust",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "py" => "Python",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "C++",
+        "cs" => "C#",
+        "swift" => "Swift",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "ex" | "exs" => "Elixir",
+        "hs" => "Haskell",
+        "scala" => "Scala",
+        "dart" => "Dart",
+        "lua" => "Lua",
+        "zig" => "Zig",
+        "nix" => "Nix",
+        "sh" | "bash" | "zsh" | "fish" => "Shell",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        "html" | "htm" => "HTML",
+        "css" | "scss" | "sass" | "less" => "CSS",
+        "sql" => "SQL",
+        "md" | "markdown" => "Markdown",
+        "toml" => "TOML",
+        "yaml" | "yml" => "YAML",
+        "json" => "JSON",
+        _ => return None,
+    };
+
+    Some(language)
+}
+
+/// Non-blank lines of a text file. Binary files have no lines.
+fn count_lines(path: &Path) -> u64 {
+    match std::fs::read_to_string(path) {
+        Ok(content) => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .count() as u64,
+        Err(_) => 0,
+    }
+}
+
+/// Files in the project that are not ignored by git and not in an artifact directory
+pub fn get_project_files(project_root: &Path, artifact_directories: &[PathBuf]) -> Vec<PathBuf> {
+    let artifact_directories = artifact_directories.to_vec();
+
+    ignore::WalkBuilder::new(project_root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            entry.file_name() != ".git"
+                && !artifact_directories.contains(&entry.path().to_path_buf())
+        })
+        .build()
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+        })
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+pub fn get_project_stats(project: &Project) -> ProjectStats {
+    let artifact_directories = clean::get_artifact_directories(project);
+    let project_files = get_project_files(&project.root, &artifact_directories);
+    let file_lines: Vec<(&'static str, u64)> = project_files
+        .par_iter()
+        .filter_map(|path| get_language(path).map(|language| (language, count_lines(path))))
+        .collect();
+    let mut languages: BTreeMap<String, LanguageStats> = BTreeMap::new();
+
+    for (language, lines) in file_lines {
+        let language_stats = languages.entry(language.to_string()).or_default();
+
+        language_stats.files += 1;
+        language_stats.lines += lines;
+    }
+
+    ProjectStats {
+        name: project.name.clone(),
+        size: clean::get_directory_size(&project.root),
+        artifact_size: artifact_directories
+            .par_iter()
+            .map(|artifact_directory| clean::get_directory_size(artifact_directory))
+            .sum(),
+        files: project_files.len() as u64,
+        languages,
+    }
+}
+
+fn show_project_stats(project_stats: &ProjectStats) {
+    println!(
+        "{}",
+        format!("Project: {}", project_stats.name)
+            .bold()
+            .underline()
+    );
+    println!(
+        "Size: {} ({} in artifacts)",
+        format_size(project_stats.size),
+        format_size(project_stats.artifact_size)
+    );
+    println!("Files: {}", project_stats.files);
+
+    if project_stats.languages.is_empty() {
+        return;
+    }
+
+    let mut languages: Vec<(&String, &LanguageStats)> = project_stats.languages.iter().collect();
+
+    languages.sort_by_key(|(_, language_stats)| Reverse(language_stats.lines));
+    println!();
+    println!("{}", "Lines of code:".bold());
+
+    for (language_name, language_stats) in languages {
+        println!(
+            "{}: {} lines in {} {}",
+            language_name,
+            language_stats.lines,
+            language_stats.files,
+            if language_stats.files == 1 {
+                "file"
+            } else {
+                "files"
+            }
+        );
+    }
+}
+
+pub fn show_stats(config: &UserConfigSchema, stats_args: &StatsArgs) {
+    if let Some(project_query) = &stats_args.project {
+        let (project_name, project_root) = resolve::resolve_project(config, project_query);
+        let project =
+            projects::get_project_for_directory(project_root.to_str()).unwrap_or_else(|| {
+                error!("Project {} does not exist", project_name);
+                std::process::exit(1)
+            });
+
+        show_project_stats(&get_project_stats(&project));
+        return;
+    }
+
+    let projects: Vec<Project> = projects::get_project_roots(config)
+        .into_iter()
+        .filter_map(|(_, project_root)| projects::get_project_or_skip(&project_root))
+        .collect();
+    let mut all_project_stats: Vec<ProjectStats> =
+        projects.par_iter().map(get_project_stats).collect();
+
+    all_project_stats.sort_by_key(|project_stats| Reverse(project_stats.size));
+
+    for project_stats in &all_project_stats {
+        println!(
+            "{} {} ({} in artifacts), {} {}, {} lines{}",
+            project_stats.name.bold(),
+            format_size(project_stats.size),
+            format_size(project_stats.artifact_size),
+            project_stats.files,
+            if project_stats.files == 1 {
+                "file"
+            } else {
+                "files"
+            },
+            project_stats.get_lines(),
+            project_stats
+                .get_main_language()
+                .map(|language_name| format!(" of {}", language_name))
+                .unwrap_or_default()
+        );
+    }
+
+    println!();
+    println!(
+        "Total: {} ({} in artifacts)",
+        format_size(all_project_stats.iter().map(|stats| stats.size).sum()),
+        format_size(
+            all_project_stats
+                .iter()
+                .map(|stats| stats.artifact_size)
+                .sum()
+        )
+    );
+}
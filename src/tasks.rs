@@ -0,0 +1,380 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, UNIX_EPOCH};
+
+use colored::Colorize;
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::environment::EnvironmentVariable;
+use crate::versions::TaskConfigSchema;
+use crate::{config, shell};
+
+/// Argument that asks a project management tool to print its tasks as JSON
+pub const TASK_DISCOVERY_ARGUMENT: &str = "--p-tasks";
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct AvailableTask {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct ToolTasksCache {
+    tool: String,
+    tool_modified: u64,
+    tasks: Vec<AvailableTask>,
+}
+
+/// Check that every dependency exists and that the tasks do not depend on each other in a cycle
+pub fn validate_tasks(tasks: &BTreeMap<String, TaskConfigSchema>) -> Result<(), String> {
+    for (task_name, task) in tasks {
+        for dependency in task.depends_on.iter().flatten() {
+            if !tasks.contains_key(dependency) {
+                return Err(format!(
+                    "Task \"{}\" depends on unknown task \"{}\"",
+                    task_name, dependency
+                ));
+            }
+        }
+    }
+
+    let mut finished: BTreeSet<&str> = BTreeSet::new();
+
+    for task_name in tasks.keys() {
+        let mut path: Vec<&str> = vec![];
+
+        find_cycle(tasks, task_name, &mut path, &mut finished)?;
+    }
+
+    Ok(())
+}
+
+fn find_cycle<'a>(
+    tasks: &'a BTreeMap<String, TaskConfigSchema>,
+    task_name: &'a str,
+    path: &mut Vec<&'a str>,
+    finished: &mut BTreeSet<&'a str>,
+) -> Result<(), String> {
+    if finished.contains(task_name) {
+        return Ok(());
+    }
+
+    if let Some(position) = path.iter().position(|name| *name == task_name) {
+        let mut cycle = path[position..].to_vec();
+        cycle.push(task_name);
+
+        return Err(format!(
+            "Tasks depend on each other: {}",
+            cycle.join(" -> ")
+        ));
+    }
+
+    path.push(task_name);
+
+    for dependency in tasks[task_name].depends_on.iter().flatten() {
+        find_cycle(tasks, dependency, path, finished)?;
+    }
+
+    path.pop();
+    finished.insert(task_name);
+
+    Ok(())
+}
+
+/// Group the target task and everything it depends on into stages. Tasks within a stage don't
+/// depend on each other and every stage only depends on the stages before it.
+pub fn get_task_stages(
+    tasks: &BTreeMap<String, TaskConfigSchema>,
+    target: &str,
+) -> Vec<Vec<String>> {
+    let mut required_tasks: BTreeSet<String> = BTreeSet::new();
+    let mut pending_tasks = vec![target.to_string()];
+
+    while let Some(task_name) = pending_tasks.pop() {
+        if required_tasks.insert(task_name.clone()) {
+            pending_tasks.extend(tasks[&task_name].depends_on.iter().flatten().cloned());
+        }
+    }
+
+    let mut stages: Vec<Vec<String>> = vec![];
+    let mut scheduled_tasks: BTreeSet<String> = BTreeSet::new();
+
+    while scheduled_tasks.len() < required_tasks.len() {
+        let stage: Vec<String> = required_tasks
+            .iter()
+            .filter(|task_name| !scheduled_tasks.contains(*task_name))
+            .filter(|task_name| {
+                tasks[*task_name]
+                    .depends_on
+                    .iter()
+                    .flatten()
+                    .all(|dependency| scheduled_tasks.contains(dependency))
+            })
+            .cloned()
+            .collect();
+
+        scheduled_tasks.extend(stage.iter().cloned());
+        stages.push(stage);
+    }
+
+    stages
+}
+
+/// Run a task after all of its dependencies, stopping at the first task that fails. Extra
+/// arguments are only passed to the target task. With `output_to_stderr`, stdout is kept free for
+/// the caller. Returns the exit code of the pipeline.
+pub fn run_task(
+    project_root: &Path,
+    tasks: &BTreeMap<String, TaskConfigSchema>,
+    target: &str,
+    arguments: &[String],
+    environment: &[EnvironmentVariable],
+    output_to_stderr: bool,
+) -> i32 {
+    for stage in get_task_stages(tasks, target) {
+        let mut children: Vec<(String, Child)> = vec![];
+
+        for task_name in stage {
+            let mut task_command = tasks[&task_name].command.clone();
+
+            if task_name == target {
+                for argument in arguments {
+                    task_command.push(' ');
+                    task_command.push_str(&shell::quote_argument(argument));
+                }
+            }
+
+            let task_header = format!("> {}: {}", task_name, task_command).bold();
+            let task_stdout = if output_to_stderr {
+                eprintln!("{}", task_header);
+                Stdio::from(std::io::stderr())
+            } else {
+                println!("{}", task_header);
+                Stdio::inherit()
+            };
+
+            let child = Command::new("sh")
+                .arg("-c")
+                .stdout(task_stdout)
+                .arg(&task_command)
+                .current_dir(project_root)
+                .envs(
+                    environment
+                        .iter()
+                        .map(|variable| (&variable.key, &variable.value)),
+                )
+                .spawn()
+                .unwrap_or_else(|_| {
+                    error!("Unable to run task {}", task_name);
+                    std::process::exit(1)
+                });
+
+            children.push((task_name, child));
+        }
+
+        if let Some((task_name, exit_code)) = wait_for_stage(&mut children) {
+            error!("Task {} failed with exit code {}", task_name, exit_code);
+
+            return exit_code;
+        }
+    }
+
+    0
+}
+
+/// Wait until every task in the stage finished. If one of them fails, the others are killed.
+fn wait_for_stage(children: &mut Vec<(String, Child)>) -> Option<(String, i32)> {
+    while !children.is_empty() {
+        let mut index = 0;
+
+        while index < children.len() {
+            let status = children[index].1.try_wait().unwrap_or_else(|_| {
+                error!("Unable to wait for task {}", children[index].0);
+                std::process::exit(1)
+            });
+
+            match status {
+                Some(status) if !status.success() => {
+                    let (task_name, _) = children.remove(index);
+
+                    for (_, child) in children.iter_mut() {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                    }
+
+                    return Some((task_name, status.code().unwrap_or(1)));
+                }
+                Some(_) => {
+                    children.remove(index);
+                }
+                None => index += 1,
+            }
+        }
+
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    None
+}
+
+/// Tasks defined by the version and project, followed by the ones advertised by the project
+/// management tool. Without a tool, the tool is not asked for its tasks.
+pub fn get_available_tasks(
+    project_name: &str,
+    project_root: &Path,
+    project_tasks: &BTreeMap<String, TaskConfigSchema>,
+    project_management_tool: Option<&str>,
+) -> Vec<AvailableTask> {
+    let mut available_tasks: Vec<AvailableTask> = project_tasks
+        .iter()
+        .map(|(task_name, task)| AvailableTask {
+            name: task_name.clone(),
+            description: task.description.clone(),
+        })
+        .collect();
+
+    let tool_tasks = match project_management_tool {
+        Some(project_management_tool) => {
+            get_tool_tasks(project_name, project_root, project_management_tool)
+        }
+        None => vec![],
+    };
+
+    for tool_task in tool_tasks {
+        if !available_tasks
+            .iter()
+            .any(|task| task.name == tool_task.name)
+        {
+            available_tasks.push(tool_task);
+        }
+    }
+
+    available_tasks
+}
+
+/// Move the cached tool tasks of a project to its new name
+pub fn rename_tool_tasks_cache(old_project_name: &str, new_project_name: &str) {
+    let tasks_cache_directory = get_tasks_cache_directory();
+    let _ = std::fs::rename(
+        tasks_cache_directory.join(format!("{}.json", old_project_name)),
+        tasks_cache_directory.join(format!("{}.json", new_project_name)),
+    );
+}
+
+pub fn remove_tool_tasks_cache(project_name: &str) {
+    let _ =
+        std::fs::remove_file(get_tasks_cache_directory().join(format!("{}.json", project_name)));
+}
+
+pub fn get_tasks_cache_directory() -> PathBuf {
+    let mut tasks_cache_directory = config::get_config_directory();
+    tasks_cache_directory.push("cache");
+    tasks_cache_directory.push("tasks");
+
+    if !tasks_cache_directory.exists() {
+        std::fs::create_dir_all(&tasks_cache_directory).unwrap_or_else(|_| {
+            error!("Unable to create tasks cache directory");
+            std::process::exit(1)
+        });
+    }
+
+    tasks_cache_directory
+}
+
+/// Ask the project management tool for its tasks. The answer is cached per project until the tool
+/// changes. Tools that don't implement the protocol simply advertise no tasks.
+pub fn get_tool_tasks(
+    project_name: &str,
+    project_root: &Path,
+    project_management_tool: &str,
+) -> Vec<AvailableTask> {
+    let tool_path = project_root.join(shellexpand::tilde(project_management_tool).into_owned());
+    let tool_modified = match std::fs::metadata(&tool_path).and_then(|metadata| metadata.modified())
+    {
+        Ok(modified) => modified
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        Err(_) => return vec![],
+    };
+    let cache_path = get_tasks_cache_directory().join(format!("{}.json", project_name));
+
+    if let Ok(cache_content) = std::fs::read_to_string(&cache_path) {
+        if let Ok(cache) = serde_json::from_str::<ToolTasksCache>(&cache_content) {
+            if cache.tool == project_management_tool && cache.tool_modified == tool_modified {
+                return cache.tasks;
+            }
+        }
+    }
+
+    let tool_output = Command::new(&tool_path)
+        .arg(TASK_DISCOVERY_ARGUMENT)
+        .current_dir(project_root)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output();
+    let tasks: Vec<AvailableTask> = match tool_output {
+        Ok(tool_output) if tool_output.status.success() => {
+            serde_json::from_slice(&tool_output.stdout).unwrap_or_default()
+        }
+        _ => vec![],
+    };
+    let cache = ToolTasksCache {
+        tool: project_management_tool.to_string(),
+        tool_modified,
+        tasks,
+    };
+    let cache_content = serde_json::to_string(&cache).unwrap_or_else(|_| {
+        error!("Unable to convert tasks cache to JSON");
+        std::process::exit(1)
+    });
+
+    std::fs::write(&cache_path, cache_content).unwrap_or_else(|_| {
+        error!("Unable to write tasks cache");
+        std::process::exit(1)
+    });
+
+    cache.tasks
+}
+
+pub fn list_tasks(available_tasks: &[AvailableTask], compact: bool) {
+    for task in available_tasks {
+        match &task.description {
+            Some(description) if !compact => println!("{} - {}", task.name, description),
+            _ => println!("{}", task.name),
+        }
+    }
+}
+
+/// Let the user choose one of the tasks. If p is not running in a terminal, the tasks are printed
+/// instead and no task is picked.
+pub fn pick_task(available_tasks: &[AvailableTask]) -> Option<String> {
+    if !std::io::stdout().is_terminal() || !std::io::stdin().is_terminal() {
+        list_tasks(available_tasks, false);
+
+        return None;
+    }
+
+    let task_strings: Vec<String> = available_tasks
+        .iter()
+        .map(|task| match &task.description {
+            Some(description) => format!("{} - {}", task.name, description),
+            None => task.name.clone(),
+        })
+        .collect();
+
+    let selected_index = dialoguer::Select::new()
+        .with_prompt("Select a task")
+        .items(&task_strings)
+        .default(0)
+        .interact_opt()
+        .unwrap_or_else(|_| {
+            error!("Unable to select a task");
+            std::process::exit(1)
+        })?;
+
+    Some(available_tasks[selected_index].name.clone())
+}
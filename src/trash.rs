@@ -0,0 +1,408 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::Args;
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{self, UserConfigSchema};
+use crate::frecency::{self, ProjectVisits};
+use crate::metadata::{self, ProjectMetadataSchema};
+use crate::{archive, history, projects, registry, resolve, tasks};
+
+/// How long removed projects are kept in the trash if trash_retention_days is not set
+const DEFAULT_TRASH_RETENTION_DAYS: u64 = 30;
+
+#[derive(Args)]
+pub struct RmArgs {
+    /// Name or alias of the project
+    pub project: String,
+    /// Also move registered projects outside the projects directory to the trash
+    #[arg(long)]
+    pub delete_files: bool,
+}
+
+#[derive(Args)]
+pub struct MvArgs {
+    /// Name or alias of the project
+    pub project: String,
+
+    /// New name of the project
+    pub new_name: String,
+
+    /// Also rename the directory of registered projects outside the projects directory
+    #[arg(long)]
+    pub move_files: bool,
+}
+
+#[derive(Args)]
+pub struct UndoArgs {}
+
+/// Everything p knew about a removed project, so `p undo` can bring it back
+#[derive(Deserialize, Serialize, Default)]
+pub struct RemovedReferences {
+    pub aliases: Vec<String>,
+    pub pinned: bool,
+    pub editor: Option<String>,
+    pub registered: bool,
+    pub metadata: Option<ProjectMetadataSchema>,
+    pub visits: Option<ProjectVisits>,
+}
+
+/// The last destructive operation, stored in ~/.p/undo.json
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "operation", rename_all = "lowercase")]
+pub enum UndoRecord {
+    Remove {
+        project: String,
+        original_path: String,
+        trash_path: String,
+        references: RemovedReferences,
+    },
+    Move {
+        old_name: String,
+        new_name: String,
+        old_path: String,
+        new_path: String,
+    },
+}
+
+pub fn get_trash_directory() -> PathBuf {
+    let trash_directory = config::get_config_directory().join("trash");
+
+    if !trash_directory.exists() {
+        std::fs::create_dir_all(&trash_directory).unwrap_or_else(|_| {
+            error!("Unable to create trash directory");
+            std::process::exit(1)
+        });
+    }
+
+    trash_directory
+}
+
+fn get_undo_path() -> PathBuf {
+    config::get_config_directory().join("undo.json")
+}
+
+fn write_undo_record(undo_record: &UndoRecord) {
+    let undo_content = serde_json::to_string(undo_record).unwrap_or_else(|_| {
+        error!("Unable to convert undo record to JSON");
+        std::process::exit(1)
+    });
+
+    std::fs::write(get_undo_path(), undo_content).unwrap_or_else(|_| {
+        error!("Unable to write undo file");
+        std::process::exit(1)
+    });
+}
+
+fn get_current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Delete projects that have been in the trash for longer than the retention period. Trashed
+/// directories are prefixed with the time they were removed at.
+fn empty_expired_trash(config: &UserConfigSchema) {
+    let retention_seconds = config
+        .trash_retention_days
+        .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS)
+        * 86_400;
+    let current_timestamp = get_current_timestamp();
+    let trash_entries = match std::fs::read_dir(get_trash_directory()) {
+        Ok(trash_entries) => trash_entries,
+        Err(_) => return,
+    };
+
+    for trash_entry in trash_entries.flatten() {
+        let trash_entry_name = trash_entry.file_name().to_string_lossy().into_owned();
+        let removed_at = trash_entry_name
+            .split_once('-')
+            .and_then(|(removed_at, _)| removed_at.parse::<u64>().ok());
+
+        if removed_at.is_some_and(|removed_at| {
+            current_timestamp.saturating_sub(removed_at) > retention_seconds
+        }) {
+            let _ = std::fs::remove_dir_all(trash_entry.path());
+        }
+    }
+}
+
+/// Point every reference p keeps to a project to its new name and root
+fn rename_project_references(old_name: &str, new_name: &str, new_root: &Path) {
+    let mut config = config::read_config();
+
+    if let Some(project_aliases) = &mut config.project_aliases {
+        for project_name in project_aliases.values_mut() {
+            if project_name == old_name {
+                *project_name = new_name.to_string();
+            }
+        }
+    }
+
+    if let Some(pinned_projects) = &mut config.pinned_projects {
+        for project_name in pinned_projects.iter_mut() {
+            if project_name == old_name {
+                *project_name = new_name.to_string();
+            }
+        }
+    }
+
+    if let Some(project_editors) = &mut config.project_editors {
+        if let Some(editor) = project_editors.remove(old_name) {
+            project_editors.insert(new_name.to_string(), editor);
+        }
+    }
+
+    if let Some(registered_projects) = &mut config.registered_projects {
+        if registered_projects.remove(old_name).is_some() {
+            registered_projects.insert(
+                new_name.to_string(),
+                new_root.to_string_lossy().into_owned(),
+            );
+        }
+    }
+
+    config::write_config(config);
+    metadata::rename_project_metadata(old_name, new_name);
+    frecency::rename_project_visits(old_name, new_name);
+    history::rename_project_history(old_name, new_name);
+    tasks::rename_tool_tasks_cache(old_name, new_name);
+}
+
+/// Remove every reference p keeps to a project, except for its history
+fn remove_project_references(project_name: &str) -> RemovedReferences {
+    let mut config = config::read_config();
+    let mut removed_references = RemovedReferences::default();
+
+    if let Some(project_aliases) = &mut config.project_aliases {
+        project_aliases.retain(|alias, aliased_project_name| {
+            if aliased_project_name == project_name {
+                removed_references.aliases.push(alias.clone());
+            }
+
+            aliased_project_name != project_name
+        });
+    }
+
+    if let Some(pinned_projects) = &mut config.pinned_projects {
+        removed_references.pinned = pinned_projects.iter().any(|pinned| pinned == project_name);
+        pinned_projects.retain(|pinned| pinned != project_name);
+    }
+
+    removed_references.editor = config
+        .project_editors
+        .as_mut()
+        .and_then(|project_editors| project_editors.remove(project_name));
+    removed_references.registered = config
+        .registered_projects
+        .as_mut()
+        .and_then(|registered_projects| registered_projects.remove(project_name))
+        .is_some();
+
+    config::write_config(config);
+    removed_references.metadata = metadata::remove_project_metadata(project_name);
+    removed_references.visits = frecency::remove_project_visits(project_name);
+    tasks::remove_tool_tasks_cache(project_name);
+
+    removed_references
+}
+
+fn restore_project_references(
+    project_name: &str,
+    project_root: &Path,
+    removed_references: RemovedReferences,
+) {
+    let mut config = config::read_config();
+
+    for alias in removed_references.aliases {
+        config
+            .project_aliases
+            .get_or_insert_with(Default::default)
+            .insert(alias, project_name.to_string());
+    }
+
+    if removed_references.pinned {
+        config
+            .pinned_projects
+            .get_or_insert_with(Vec::new)
+            .push(project_name.to_string());
+    }
+
+    if let Some(editor) = removed_references.editor {
+        config
+            .project_editors
+            .get_or_insert_with(Default::default)
+            .insert(project_name.to_string(), editor);
+    }
+
+    if removed_references.registered {
+        config
+            .registered_projects
+            .get_or_insert_with(Default::default)
+            .insert(
+                project_name.to_string(),
+                project_root.to_string_lossy().into_owned(),
+            );
+    }
+
+    config::write_config(config);
+
+    if let Some(project_metadata) = removed_references.metadata {
+        metadata::restore_project_metadata(project_name, project_metadata);
+    }
+
+    if let Some(project_visits) = removed_references.visits {
+        frecency::restore_project_visits(project_name, project_visits);
+    }
+}
+
+pub fn remove_project(config: &UserConfigSchema, rm_args: &RmArgs) {
+    let (project_name, project_root) = resolve::resolve_exact_project(config, &rm_args.project);
+
+    if !projects::is_in_projects_directory(config, &project_root) && !rm_args.delete_files {
+        error!(
+            "{} lives outside the projects directory in {}. Use `p unregister {}` to forget it or `p rm --delete-files {}` to move it to the trash",
+            project_name,
+            project_root.display(),
+            project_name,
+            project_name
+        );
+        std::process::exit(1)
+    }
+
+    empty_expired_trash(config);
+
+    let trash_path =
+        get_trash_directory().join(format!("{}-{}", get_current_timestamp(), project_name));
+
+    archive::move_directory(&project_root, &trash_path).unwrap_or_else(|e| {
+        error!("Unable to move {} to the trash: {}", project_name, e);
+        std::process::exit(1)
+    });
+
+    let removed_references = remove_project_references(&project_name);
+
+    write_undo_record(&UndoRecord::Remove {
+        project: project_name.clone(),
+        original_path: project_root.to_string_lossy().into_owned(),
+        trash_path: trash_path.to_string_lossy().into_owned(),
+        references: removed_references,
+    });
+
+    println!("Moved {} to {}", project_name, trash_path.display());
+}
+
+fn move_project(old_name: &str, new_name: &str, old_root: &Path, new_root: &Path) {
+    // Registered projects can be renamed without touching their directory
+    if old_root != new_root {
+        if new_root.exists() {
+            error!("{} already exists", new_root.display());
+            std::process::exit(1)
+        }
+
+        archive::move_directory(old_root, new_root).unwrap_or_else(|e| {
+            error!("Unable to move {}: {}", old_name, e);
+            std::process::exit(1)
+        });
+    }
+
+    rename_project_references(old_name, new_name, new_root);
+}
+
+pub fn rename_project(config: &UserConfigSchema, mv_args: &MvArgs) {
+    let (old_name, old_root) = resolve::resolve_exact_project(config, &mv_args.project);
+    let new_name = &mv_args.new_name;
+
+    if !projects::is_valid_project_name(new_name) {
+        error!("{} is not a valid project name", new_name);
+        std::process::exit(1)
+    }
+
+    if projects::get_project_root(config, new_name).is_some() {
+        error!("A project called {} already exists", new_name);
+        std::process::exit(1)
+    }
+
+    // Registered projects outside the projects directory only get a new name unless their
+    // directory should be renamed too, nested clones are renamed in place and all others stay in
+    // the projects directory
+    let new_root = if !projects::is_in_projects_directory(config, &old_root) {
+        if mv_args.move_files {
+            old_root.with_file_name(new_name)
+        } else {
+            old_root.clone()
+        }
+    } else if registry::is_registered(config, &old_name) {
+        old_root.with_file_name(new_name)
+    } else {
+        PathBuf::from(shellexpand::tilde(&config.projects_dir).into_owned()).join(new_name)
+    };
+
+    move_project(&old_name, new_name, &old_root, &new_root);
+    write_undo_record(&UndoRecord::Move {
+        old_name: old_name.clone(),
+        new_name: new_name.clone(),
+        old_path: old_root.to_string_lossy().into_owned(),
+        new_path: new_root.to_string_lossy().into_owned(),
+    });
+
+    println!("{}", new_root.display());
+}
+
+pub fn undo_last_operation() {
+    let undo_path = get_undo_path();
+    let undo_record: UndoRecord = std::fs::read_to_string(&undo_path)
+        .ok()
+        .and_then(|undo_content| serde_json::from_str(&undo_content).ok())
+        .unwrap_or_else(|| {
+            error!("Nothing to undo");
+            std::process::exit(1)
+        });
+
+    match undo_record {
+        UndoRecord::Remove {
+            project,
+            original_path,
+            trash_path,
+            references,
+        } => {
+            let original_path = PathBuf::from(original_path);
+
+            if original_path.exists() {
+                error!("{} already exists", original_path.display());
+                std::process::exit(1)
+            }
+
+            archive::move_directory(Path::new(&trash_path), &original_path).unwrap_or_else(|e| {
+                error!("Unable to restore {}: {}", project, e);
+                std::process::exit(1)
+            });
+            restore_project_references(&project, &original_path, references);
+
+            println!("Restored {} to {}", project, original_path.display());
+        }
+        UndoRecord::Move {
+            old_name,
+            new_name,
+            old_path,
+            new_path,
+        } => {
+            move_project(
+                &new_name,
+                &old_name,
+                Path::new(&new_path),
+                Path::new(&old_path),
+            );
+
+            println!("Renamed {} back to {}", new_name, old_name);
+        }
+    }
+
+    std::fs::remove_file(undo_path).unwrap_or_else(|_| {
+        error!("Unable to remove undo file");
+        std::process::exit(1)
+    });
+}
@@ -1,8 +1,9 @@
 use log::error;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-use crate::{config::get_config_directory, repositories};
+use crate::{config::get_config_directory, repositories, tasks};
 
 #[derive(Deserialize, Serialize)]
 pub struct VersionConfigSchema {
@@ -12,6 +13,19 @@ pub struct VersionConfigSchema {
     pub directories_needed: Vec<String>,
     pub specificity: u8,
     pub project_management_tool: Option<String>,
+    pub env_files: Option<Vec<String>>,
+    pub tasks: Option<BTreeMap<String, TaskConfigSchema>>,
+    pub watch_ignore: Option<Vec<String>>,
+    pub editor: Option<String>,
+    pub scaffold: Option<String>,
+    pub artifact_dirs: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct TaskConfigSchema {
+    pub command: String,
+    pub description: Option<String>,
+    pub depends_on: Option<Vec<String>>,
 }
 
 pub fn get_versions_directory() -> PathBuf {
@@ -39,7 +53,7 @@ pub fn get_current_directory_versions() -> Vec<VersionConfigSchema> {
     directory_versions
 }
 
-pub fn get_directory_versions(directory: &PathBuf) -> Vec<VersionConfigSchema> {
+pub fn get_all_versions() -> Vec<VersionConfigSchema> {
     let versions_directory = get_versions_directory();
     let versions_configs = std::fs::read_dir(versions_directory)
         .unwrap_or_else(|_| {
@@ -49,9 +63,8 @@ pub fn get_directory_versions(directory: &PathBuf) -> Vec<VersionConfigSchema> {
         .filter(|entry| entry.as_ref().unwrap().path().extension().unwrap() == "toml");
     let external_versions_configs = repositories::get_repositories_configs();
     let all_versions_configs = versions_configs.chain(external_versions_configs);
-    let mut directory_versions: Vec<VersionConfigSchema> = vec![];
+    let mut all_versions: Vec<VersionConfigSchema> = vec![];
 
-    // Loop through all known versions configs to find which ones match the current directory
     for version_config in all_versions_configs {
         let version_config_content = std::fs::read_to_string(
             version_config
@@ -71,6 +84,33 @@ pub fn get_directory_versions(directory: &PathBuf) -> Vec<VersionConfigSchema> {
                 std::process::exit(1)
             });
 
+        if let Some(version_tasks) = &version_config_parsed.tasks {
+            tasks::validate_tasks(version_tasks).unwrap_or_else(|e| {
+                error!(
+                    "Invalid tasks in version {}: {}",
+                    version_config_parsed.version, e
+                );
+                std::process::exit(1)
+            });
+        }
+
+        all_versions.push(version_config_parsed);
+    }
+
+    all_versions
+}
+
+pub fn get_version_by_name(version_name: &str) -> Option<VersionConfigSchema> {
+    get_all_versions()
+        .into_iter()
+        .find(|version| version.version.eq_ignore_ascii_case(version_name))
+}
+
+pub fn get_directory_versions(directory: &PathBuf) -> Vec<VersionConfigSchema> {
+    let mut directory_versions: Vec<VersionConfigSchema> = vec![];
+
+    // Loop through all known versions configs to find which ones match the current directory
+    for version_config_parsed in get_all_versions() {
         let mut files_needed = version_config_parsed.files_needed.clone();
         let mut directories_needed = version_config_parsed.directories_needed.clone();
         let mut should_include_version_in_versions = true;
@@ -112,6 +152,12 @@ pub fn get_directory_versions(directory: &PathBuf) -> Vec<VersionConfigSchema> {
             directories_needed: vec![],
             specificity: 0,
             project_management_tool: None,
+            env_files: None,
+            tasks: None,
+            watch_ignore: None,
+            editor: None,
+            scaffold: None,
+            artifact_dirs: None,
         });
 
         directory_versions
@@ -142,6 +188,12 @@ pub fn create_sample_version_in_versions_directory() {
         directories_needed: vec!["src".to_string()],
         specificity: 1,
         project_management_tool: Some("./project".to_string()),
+        env_files: None,
+        tasks: None,
+        watch_ignore: None,
+        editor: None,
+        scaffold: None,
+        artifact_dirs: None,
     };
     let version_config = toml::to_string(&version_config).unwrap_or_else(|_| {
         error!("Unable to convert version config to TOML");
@@ -0,0 +1,388 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use log::error;
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::versions::VersionConfigSchema;
+use crate::ExecuteArgs;
+
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(300);
+const STOP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Decides which changed files should trigger a rerun
+struct WatchFilter {
+    root: PathBuf,
+    /// The root .gitignore, .git/info/exclude and the watch_ignore patterns of the version
+    ignored: Gitignore,
+    /// .gitignore files of subdirectories, read when a change in the directory comes in
+    nested_ignored: RefCell<HashMap<PathBuf, Option<Gitignore>>>,
+    globs: Option<GlobSet>,
+}
+
+impl WatchFilter {
+    fn new(root: &Path, watch_ignore_patterns: &[String], globs: Option<GlobSet>) -> WatchFilter {
+        WatchFilter {
+            root: root.to_path_buf(),
+            ignored: get_ignored_files(root, watch_ignore_patterns),
+            nested_ignored: RefCell::new(HashMap::new()),
+            globs,
+        }
+    }
+
+    /// Whether a .gitignore below the root ignores the path. Like git, the .gitignore closest to
+    /// the path wins, so it can also re-include files ignored further up.
+    fn get_nested_match(&self, path: &Path, is_directory: bool) -> Option<bool> {
+        let mut nested_ignored = self.nested_ignored.borrow_mut();
+
+        for directory in path.ancestors().skip(1) {
+            if directory == self.root || !directory.starts_with(&self.root) {
+                break;
+            }
+
+            let directory_ignored = nested_ignored
+                .entry(directory.to_path_buf())
+                .or_insert_with(|| {
+                    let gitignore_path = directory.join(".gitignore");
+
+                    if !gitignore_path.is_file() {
+                        return None;
+                    }
+
+                    let mut ignored_builder = GitignoreBuilder::new(directory);
+
+                    ignored_builder.add(gitignore_path);
+                    ignored_builder.build().ok()
+                });
+
+            if let Some(directory_ignored) = directory_ignored {
+                let matched = directory_ignored.matched_path_or_any_parents(path, is_directory);
+
+                if matched.is_ignore() {
+                    return Some(true);
+                }
+
+                if matched.is_whitelist() {
+                    return Some(false);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn is_relevant(&self, path: &Path) -> bool {
+        let relative_path = match path.strip_prefix(&self.root) {
+            Ok(relative_path) => relative_path,
+            Err(_) => return false,
+        };
+
+        if relative_path.starts_with(".git") {
+            return false;
+        }
+
+        let is_directory = path.is_dir();
+        let is_ignored = self
+            .get_nested_match(path, is_directory)
+            .unwrap_or_else(|| {
+                self.ignored
+                    .matched_path_or_any_parents(relative_path, is_directory)
+                    .is_ignore()
+            });
+
+        if is_ignored {
+            return false;
+        }
+
+        match &self.globs {
+            Some(globs) => globs.is_match(relative_path),
+            None => true,
+        }
+    }
+}
+
+/// Run `p execute` with the given arguments every time a file in the project root changes
+pub fn watch_project(
+    project_root: &Path,
+    project_version: &VersionConfigSchema,
+    execute_args: &ExecuteArgs,
+    arguments: &[String],
+) {
+    let project_root = std::fs::canonicalize(project_root).unwrap_or_else(|_| {
+        error!("Unable to resolve project root {}", project_root.display());
+        std::process::exit(1)
+    });
+    let watch_filter = WatchFilter::new(
+        &project_root,
+        project_version.watch_ignore.as_deref().unwrap_or_default(),
+        get_watch_globs(&execute_args.glob),
+    );
+    let (sender, receiver) = channel();
+    let mut watcher = notify::recommended_watcher(sender).unwrap_or_else(|e| {
+        error!("Unable to watch project: {}", e);
+        std::process::exit(1)
+    });
+
+    watcher
+        .watch(&project_root, RecursiveMode::Recursive)
+        .unwrap_or_else(|e| {
+            error!("Unable to watch project: {}", e);
+            std::process::exit(1)
+        });
+
+    // The children run in their own process group, so they have to be stopped on Ctrl-C as well
+    let running_process_group = Arc::new(AtomicU32::new(0));
+    let handler_process_group = running_process_group.clone();
+
+    ctrlc::set_handler(move || {
+        let process_group = handler_process_group.load(Ordering::SeqCst);
+
+        if process_group != 0 {
+            signal_process_group(process_group, "KILL");
+        }
+
+        std::process::exit(130);
+    })
+    .unwrap_or_else(|_| {
+        error!("Unable to set Ctrl-C handler");
+        std::process::exit(1)
+    });
+
+    loop {
+        let mut child = spawn_execution(execute_args, arguments);
+
+        running_process_group.store(child.id(), Ordering::SeqCst);
+        wait_for_changes(&receiver, &watch_filter, &mut child);
+        stop_execution(&mut child);
+        running_process_group.store(0, Ordering::SeqCst);
+
+        println!("{}", "Change detected, restarting...".bold());
+    }
+}
+
+fn get_ignored_files(project_root: &Path, watch_ignore_patterns: &[String]) -> Gitignore {
+    let mut ignored_builder = GitignoreBuilder::new(project_root);
+
+    ignored_builder.add(project_root.join(".gitignore"));
+    ignored_builder.add(project_root.join(".git").join("info").join("exclude"));
+
+    for watch_ignore_pattern in watch_ignore_patterns {
+        ignored_builder
+            .add_line(None, watch_ignore_pattern)
+            .unwrap_or_else(|e| {
+                error!(
+                    "Invalid watch ignore pattern {}: {}",
+                    watch_ignore_pattern, e
+                );
+                std::process::exit(1)
+            });
+    }
+
+    ignored_builder.build().unwrap_or_else(|e| {
+        error!("Unable to read ignore files: {}", e);
+        std::process::exit(1)
+    })
+}
+
+fn get_watch_globs(patterns: &[String]) -> Option<GlobSet> {
+    if patterns.is_empty() {
+        return None;
+    }
+
+    let mut globs_builder = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        globs_builder.add(Glob::new(pattern).unwrap_or_else(|e| {
+            error!("Invalid glob {}: {}", pattern, e);
+            std::process::exit(1)
+        }));
+    }
+
+    Some(globs_builder.build().unwrap_or_else(|e| {
+        error!("Invalid globs: {}", e);
+        std::process::exit(1)
+    }))
+}
+
+/// Run the same `p execute` invocation without --watch in a new process group
+fn spawn_execution(execute_args: &ExecuteArgs, arguments: &[String]) -> Child {
+    let current_executable = std::env::current_exe().unwrap_or_else(|_| {
+        error!("Unable to find the p executable");
+        std::process::exit(1)
+    });
+    let mut command = Command::new(current_executable);
+
+    command.arg("execute");
+    if let Some(project) = &execute_args.project {
+        command.arg("--project").arg(project);
+    }
+    for env_file in &execute_args.env_file {
+        command.arg("--env-file").arg(env_file);
+    }
+    if execute_args.no_env {
+        command.arg("--no-env");
+    }
+    command.arg("--").args(arguments);
+    command.process_group(0);
+
+    command.spawn().unwrap_or_else(|_| {
+        error!("Error executing command in current project");
+        std::process::exit(1)
+    })
+}
+
+/// Block until a relevant file changed and no further changes came in for the debounce duration
+fn wait_for_changes(
+    receiver: &Receiver<notify::Result<notify::Event>>,
+    watch_filter: &WatchFilter,
+    child: &mut Child,
+) {
+    let mut child_running = true;
+    let mut last_change: Option<Instant> = None;
+
+    loop {
+        let timeout = match last_change {
+            Some(last_change) => DEBOUNCE_DURATION.saturating_sub(last_change.elapsed()),
+            None => Duration::from_millis(200),
+        };
+
+        match receiver.recv_timeout(timeout) {
+            Ok(Ok(event)) => {
+                if matches!(event.kind, EventKind::Access(_)) {
+                    continue;
+                }
+
+                if event
+                    .paths
+                    .iter()
+                    .any(|path| watch_filter.is_relevant(path))
+                {
+                    last_change = Some(Instant::now());
+                }
+            }
+            Ok(Err(e)) => {
+                error!("Error while watching project: {}", e);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if let Some(last_change) = last_change {
+                    if last_change.elapsed() >= DEBOUNCE_DURATION {
+                        return;
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                error!("Stopped watching project");
+                std::process::exit(1)
+            }
+        }
+
+        if child_running {
+            if let Ok(Some(status)) = child.try_wait() {
+                child_running = false;
+                println!(
+                    "{}",
+                    format!(
+                        "Exited with code {}, waiting for changes...",
+                        status.code().unwrap_or(1)
+                    )
+                    .bold()
+                );
+            }
+        }
+    }
+}
+
+/// Stop the child and everything it started, first politely and then forcefully
+fn stop_execution(child: &mut Child) {
+    if let Ok(Some(_)) = child.try_wait() {
+        return;
+    }
+
+    signal_process_group(child.id(), "TERM");
+
+    let stop_started = Instant::now();
+
+    while stop_started.elapsed() < STOP_TIMEOUT {
+        if let Ok(Some(_)) = child.try_wait() {
+            return;
+        }
+
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    signal_process_group(child.id(), "KILL");
+    let _ = child.wait();
+}
+
+fn signal_process_group(process_group: u32, signal: &str) {
+    let _ = Command::new("kill")
+        .arg(format!("-{}", signal))
+        .arg("--")
+        .arg(format!("-{}", process_group))
+        .status();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn watch_filter_ignores_files() {
+        let project_directory = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(project_directory.path()).unwrap();
+
+        write_file(&root.join(".gitignore"), "target\n");
+        write_file(
+            &root.join(".git").join("info").join("exclude"),
+            "secret.txt\n",
+        );
+        write_file(
+            &root.join("packages").join("web").join(".gitignore"),
+            "dist\n!keep.log\n",
+        );
+
+        let watch_filter = WatchFilter::new(&root, &["*.log".to_string()], None);
+
+        assert!(watch_filter.is_relevant(&root.join("src").join("main.rs")));
+        assert!(!watch_filter.is_relevant(&root.join("target").join("debug").join("p")));
+        assert!(!watch_filter.is_relevant(&root.join("secret.txt")));
+        assert!(!watch_filter.is_relevant(&root.join(".git").join("HEAD")));
+        assert!(!watch_filter.is_relevant(&root.join("build.log")));
+        assert!(!watch_filter.is_relevant(
+            &root
+                .join("packages")
+                .join("web")
+                .join("dist")
+                .join("index.js")
+        ));
+        assert!(watch_filter.is_relevant(&root.join("packages").join("web").join("keep.log")));
+        assert!(watch_filter.is_relevant(&root.join("packages").join("api").join("dist")));
+        assert!(!watch_filter.is_relevant(Path::new("/somewhere/else")));
+    }
+
+    #[test]
+    fn watch_filter_matches_globs() {
+        let project_directory = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(project_directory.path()).unwrap();
+        let watch_filter =
+            WatchFilter::new(&root, &[], get_watch_globs(&["src/**/*.rs".to_string()]));
+
+        assert!(watch_filter.is_relevant(&root.join("src").join("main.rs")));
+        assert!(!watch_filter.is_relevant(&root.join("README.md")));
+    }
+}
@@ -1,6 +1,42 @@
 use assert_cmd::prelude::*;
 use predicates::prelude::*;
 use std::process::Command;
+use tempfile::TempDir;
+
+/// Create a home directory with a p config and a single Rust project called "demo"
+fn setup_home() -> Result<TempDir, Box<dyn std::error::Error>> {
+    let home = tempfile::tempdir()?;
+    let config_directory = home.path().join(".p");
+    let versions_directory = config_directory.join("versions");
+    let project_directory = home.path().join("Projects").join("demo");
+
+    std::fs::create_dir_all(&versions_directory)?;
+    std::fs::create_dir_all(project_directory.join("src"))?;
+    std::fs::write(
+        config_directory.join("config.toml"),
+        format!(
+            "projects_dir = \"{}\"\nproject_management_tool = \"./project\"\n",
+            home.path().join("Projects").display()
+        ),
+    )?;
+    std::fs::write(
+        versions_directory.join("rust.toml"),
+        "version = \"Rust\"\ndescription = \"A Rust project\"\nfiles_needed = [\"Cargo.toml\"]\ndirectories_needed = [\"src\"]\nspecificity = 1\n",
+    )?;
+    std::fs::write(project_directory.join("Cargo.toml"), "")?;
+
+    Ok(home)
+}
+
+fn p_in(home: &TempDir, directory: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("p")?;
+
+    cmd.env("HOME", home.path());
+    cmd.env("NO_COLOR", "1");
+    cmd.current_dir(home.path().join(directory));
+
+    Ok(cmd)
+}
 
 #[test]
 fn help_test() -> Result<(), Box<dyn std::error::Error>> {
@@ -13,3 +49,1279 @@ fn help_test() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn explain_env_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let project_directory = home.path().join("Projects").join("demo");
+
+    std::fs::write(project_directory.join(".env"), "P_TEST_A=1\nP_TEST_B=2\n")?;
+    std::fs::write(project_directory.join(".env.local"), "P_TEST_B=3\n")?;
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.arg("execute").arg("--explain-env");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("P_TEST_A from .env\n"))
+        .stdout(predicate::str::contains("P_TEST_B from .env.local"));
+
+    Ok(())
+}
+
+#[test]
+fn task_cycle_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let version_path = home.path().join(".p").join("versions").join("rust.toml");
+    let mut version_config = std::fs::read_to_string(&version_path)?;
+
+    version_config.push_str(
+        "[tasks.build]\ncommand = \"true\"\ndepends_on = [\"test\"]\n\n[tasks.test]\ncommand = \"true\"\ndepends_on = [\"build\"]\n",
+    );
+    std::fs::write(&version_path, version_config)?;
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.arg("execute").arg("test");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("build -> test -> build"));
+
+    Ok(())
+}
+
+#[test]
+fn task_dependencies_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let project_directory = home.path().join("Projects").join("demo");
+    let version_path = home.path().join(".p").join("versions").join("rust.toml");
+    let mut version_config = std::fs::read_to_string(&version_path)?;
+
+    // test only fails with its own exit code if build ran before it
+    version_config.push_str(
+        "[tasks.build]\ncommand = \"touch built\"\n\n[tasks.test]\ncommand = \"test -f built && exit 3\"\ndepends_on = [\"build\"]\n\n[tasks.deploy]\ncommand = \"touch deployed\"\ndepends_on = [\"test\"]\n",
+    );
+    std::fs::write(&version_path, version_config)?;
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["execute", "deploy"]);
+    cmd.assert().code(3);
+
+    assert!(project_directory.join("built").exists());
+    assert!(!project_directory.join("deployed").exists());
+
+    Ok(())
+}
+
+#[test]
+fn history_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let version_path = home.path().join(".p").join("versions").join("rust.toml");
+    let mut version_config = std::fs::read_to_string(&version_path)?;
+
+    version_config
+        .push_str("[tasks.build]\ncommand = \"true\"\n\n[tasks.lint]\ncommand = \"false\"\n");
+    std::fs::write(&version_path, version_config)?;
+
+    p_in(&home, "Projects/demo")?
+        .args(["execute", "build"])
+        .assert()
+        .success();
+    p_in(&home, "Projects/demo")?
+        .args(["execute", "lint"])
+        .assert()
+        .failure();
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["history", "--task", "lint"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("demo lint: 1 run"))
+        .stdout(predicate::str::contains("100% failed"))
+        .stdout(predicate::str::contains("demo build").not());
+
+    Ok(())
+}
+
+#[test]
+fn execute_without_arguments_lists_tasks_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let version_path = home.path().join(".p").join("versions").join("rust.toml");
+    let mut version_config = std::fs::read_to_string(&version_path)?;
+
+    version_config.push_str(
+        "[tasks.build]\ncommand = \"true\"\ndescription = \"Compile the project\"\n\n[tasks.test]\ncommand = \"true\"\n",
+    );
+    std::fs::write(&version_path, version_config)?;
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.arg("execute");
+    cmd.assert()
+        .success()
+        .stdout("build - Compile the project\ntest\n");
+
+    Ok(())
+}
+
+#[test]
+fn tool_task_discovery_test() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let home = setup_home()?;
+    let tool_path = home.path().join("Projects").join("demo").join("project");
+
+    std::fs::write(
+        &tool_path,
+        "#!/bin/sh\n[ \"$1\" = \"--p-tasks\" ] && echo '[{\"name\": \"release\", \"description\": \"Cut a release\"}]'\n",
+    )?;
+    std::fs::set_permissions(&tool_path, std::fs::Permissions::from_mode(0o755))?;
+
+    // A plain p info doesn't run the tool
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.arg("info");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("release").not());
+
+    assert!(!home.path().join(".p/cache/tasks/demo.json").exists());
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["info", "--only", "tasks"]);
+    cmd.assert()
+        .success()
+        .stdout("Project: demo\n1 Task:\nrelease (from project management tool)\n");
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["execute", "--list"]);
+    cmd.assert().success().stdout("release - Cut a release\n");
+
+    assert!(home.path().join(".p/cache/tasks/demo.json").exists());
+
+    // Discovery can be turned off, so the tool of the project is never run to find tasks
+    let config_path = home.path().join(".p").join("config.toml");
+    let config_content = std::fs::read_to_string(&config_path)?;
+
+    std::fs::write(
+        &config_path,
+        format!("{}discover_tool_tasks = false\n", config_content),
+    )?;
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["execute", "--list"]);
+    cmd.assert().success().stdout("");
+
+    Ok(())
+}
+
+#[test]
+fn edit_opens_project_root_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let project_directory = home.path().join("Projects").join("demo");
+    let mut cmd = p_in(&home, "Projects/demo/src")?;
+
+    cmd.args([
+        "edit",
+        "--editor",
+        "echo {root}/{file}",
+        "--file",
+        "src/main.rs",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(format!("{}/src/main.rs\n", project_directory.display()));
+
+    Ok(())
+}
+
+#[test]
+fn project_config_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let project_directory = home.path().join("Projects").join("pinned");
+
+    std::fs::create_dir_all(&project_directory)?;
+    std::fs::write(
+        project_directory.join(".p.toml"),
+        "version = \"Rust\"\ndescription = \"A pinned project\"\n\n[env]\nP_TEST_GREETING = \"hello\"\n\n[tasks.greet]\ncommand = \"echo $P_TEST_GREETING\"\n",
+    )?;
+
+    let mut info_cmd = p_in(&home, "Projects/pinned")?;
+
+    info_cmd.arg("info");
+    info_cmd
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("A pinned project"))
+        .stdout(predicate::str::contains("Version: Rust"))
+        .stdout(predicate::str::contains("greet (from .p.toml)"));
+
+    let mut execute_cmd = p_in(&home, "Projects/pinned")?;
+
+    execute_cmd.args(["execute", "greet"]);
+    execute_cmd
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hello\n"));
+
+    Ok(())
+}
+
+#[test]
+fn tags_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+
+    std::fs::create_dir_all(home.path().join("Projects").join("other"))?;
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["tag", "add", "demo", "oncall", "client:acme"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["list", "--tag", "oncall"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("demo"))
+        .stdout(predicate::str::contains("other").not());
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["tag", "add", "missing", "oncall"]);
+    cmd.assert().failure();
+
+    // Names can't point outside of the projects directory
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["describe", "../Projects", "Not a project"]);
+    cmd.assert().failure();
+
+    Ok(())
+}
+
+#[test]
+fn alias_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+
+    std::fs::create_dir_all(home.path().join("Projects").join("other"))?;
+    std::fs::write(
+        home.path().join("Projects").join("demo").join(".p.toml"),
+        "[tasks.greet]\ncommand = \"echo hello\"\n",
+    )?;
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["alias", "add", "d", "demo"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["go", "d"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::ends_with("demo\n"));
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["execute", "--project", "d", "--list", "--compact"]);
+    cmd.assert().success().stdout("greet\n");
+
+    // An alias for another project is only replaced with --force
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["alias", "add", "d", "other"]);
+    cmd.assert().failure();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["go", "d"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::ends_with("demo\n"));
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["alias", "add", "d", "other", "--force"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["go", "d"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::ends_with("other\n"));
+
+    Ok(())
+}
+
+#[test]
+fn register_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let dotfiles_directory = home.path().join("dotfiles");
+
+    std::fs::create_dir_all(&dotfiles_directory)?;
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["register", "../../dotfiles", "--name", "dots"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.arg("list");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("dots (Unknown)"));
+
+    let mut cmd = p_in(&home, "dotfiles")?;
+
+    cmd.args(["execute", "--list"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["unregister", "dots"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["go", "dots"]);
+    cmd.assert().failure();
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["register", "../../dotfiles", "--name", "a/b"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("a/b is not a valid project name"));
+
+    // A registered ancestor of the projects directory doesn't take over the projects inside it
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["register", home.path().to_str().unwrap(), "--name", "home"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects/demo/src")?;
+
+    cmd.args(["info", "--only", "root"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("Project: demo\n"));
+
+    let mut cmd = p_in(&home, "dotfiles")?;
+
+    cmd.args(["info", "--only", "root"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("Project: home\n"));
+
+    Ok(())
+}
+
+#[test]
+fn clone_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let repository_directory = home.path().join("widget");
+
+    std::fs::create_dir_all(&repository_directory)?;
+    Command::new("git")
+        .arg("init")
+        .arg("-q")
+        .current_dir(&repository_directory)
+        .status()?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["clone", repository_directory.to_str().unwrap(), "gadget"]);
+    cmd.assert().success().stdout(format!(
+        "{}\n",
+        home.path().join("Projects").join("gadget").display()
+    ));
+
+    // The name can't point outside of the projects directory
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args([
+        "clone",
+        repository_directory.to_str().unwrap(),
+        "../escaped",
+    ]);
+    cmd.assert().failure();
+
+    assert!(!home.path().join("escaped").exists());
+
+    // Neither may it create a hidden directory, also when it comes from the URL
+    let hidden_repository_directory = home.path().join(".hidden");
+
+    std::fs::create_dir_all(&hidden_repository_directory)?;
+    Command::new("git")
+        .arg("init")
+        .arg("-q")
+        .current_dir(&hidden_repository_directory)
+        .status()?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["clone", hidden_repository_directory.to_str().unwrap()]);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        ".hidden is not a valid project name",
+    ));
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["clone", repository_directory.to_str().unwrap(), ".gadget"]);
+    cmd.assert().failure();
+
+    assert!(!home.path().join("Projects").join(".hidden").exists());
+    assert!(!home.path().join("Projects").join(".gadget").exists());
+
+    // Host directories of nested clones are not listed as projects
+    let nested_directory = home
+        .path()
+        .join("Projects")
+        .join("github.com")
+        .join("acme")
+        .join("widget");
+
+    std::fs::create_dir_all(&nested_directory)?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["register", nested_directory.to_str().unwrap()]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.arg("list");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("widget"))
+        .stdout(predicate::str::contains("github.com").not());
+
+    Ok(())
+}
+
+#[test]
+fn new_project_from_template_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let template_directory = home.path().join(".p").join("templates").join("rust");
+
+    std::fs::create_dir_all(template_directory.join("src"))?;
+    std::fs::write(
+        template_directory.join("Cargo.toml"),
+        "[package]\nname = \"{{name}}\"\n",
+    )?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["new", "widget", "--version", "rust"]);
+    cmd.assert().success();
+
+    let project_directory = home.path().join("Projects").join("widget");
+
+    assert_eq!(
+        std::fs::read_to_string(project_directory.join("Cargo.toml"))?,
+        "[package]\nname = \"widget\"\n"
+    );
+    assert!(project_directory.join("src").is_dir());
+
+    for invalid_name in [".hidden", "a/b", ".."] {
+        let mut cmd = p_in(&home, "Projects")?;
+
+        cmd.args(["new", invalid_name, "--version", "rust"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("is not a valid project name"));
+    }
+
+    assert!(!home.path().join("Projects").join(".hidden").exists());
+
+    Ok(())
+}
+
+#[test]
+fn archive_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["archive", "demo", "--compress"]);
+    cmd.assert().success();
+
+    assert!(!home.path().join("Projects").join("demo").exists());
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "demo", "--archived"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("demo (archived)"));
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["unarchive", "demo"]);
+    cmd.assert().success();
+
+    assert!(home
+        .path()
+        .join("Projects")
+        .join("demo")
+        .join("Cargo.toml")
+        .exists());
+
+    // A project called like the list subcommand can be archived after --
+    std::fs::create_dir_all(home.path().join("Projects").join("list"))?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["archive", "--", "list"]);
+    cmd.assert().success().stdout("Archived list\n");
+
+    assert!(!home.path().join("Projects").join("list").exists());
+
+    // A failed archive leaves the project and no manifest behind
+    let archive_directory = home.path().join(".p").join("archive");
+
+    std::fs::create_dir_all(archive_directory.join("demo.tar.zst"))?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["archive", "demo", "--compress"]);
+    cmd.assert().failure();
+
+    assert!(home.path().join("Projects").join("demo").exists());
+    assert!(!archive_directory.join("demo.toml").exists());
+
+    // A leftover directory in the archive is never merged with the project
+    std::fs::create_dir_all(archive_directory.join("demo").join("leftover"))?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["archive", "demo"]);
+    cmd.assert().failure();
+
+    assert!(home
+        .path()
+        .join("Projects")
+        .join("demo")
+        .join("Cargo.toml")
+        .exists());
+    assert!(!archive_directory.join("demo").join("Cargo.toml").exists());
+
+    Ok(())
+}
+
+#[test]
+fn archive_registered_project_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let dotfiles_directory = home.path().join("dotfiles");
+
+    std::fs::create_dir(&dotfiles_directory)?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["register", dotfiles_directory.to_str().unwrap()]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["archive", "dotfiles"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--move-files"));
+
+    assert!(dotfiles_directory.exists());
+
+    // The registration is removed while archived and restored when unarchived
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["archive", "dotfiles", "--move-files"]);
+    cmd.assert().success();
+
+    assert!(!dotfiles_directory.exists());
+    assert!(
+        !std::fs::read_to_string(home.path().join(".p").join("config.toml"))?.contains("dotfiles")
+    );
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["unarchive", "dotfiles"]);
+    cmd.assert().success();
+
+    assert!(dotfiles_directory.exists());
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["go", "dotfiles"]);
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn rm_and_undo_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let project_directory = home.path().join("Projects").join("demo");
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["alias", "add", "d", "demo"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["rm", "d"]);
+    cmd.assert().success();
+
+    assert!(!project_directory.exists());
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["alias", "list"]);
+    cmd.assert().success().stdout("");
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.arg("undo");
+    cmd.assert().success();
+
+    assert!(project_directory.join("Cargo.toml").exists());
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["go", "d"]);
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn rm_requires_exact_name_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let project_directory = home.path().join("Projects").join("customer-portal");
+
+    std::fs::create_dir(&project_directory)?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["rm", "cutsomer"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Project cutsomer does not exist"))
+        .stderr(predicate::str::contains("  customer-portal"));
+
+    assert!(project_directory.exists());
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["mv", "customer", "portal"]);
+    cmd.assert().failure();
+
+    assert!(project_directory.exists());
+
+    Ok(())
+}
+
+#[test]
+fn rm_registered_project_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let dotfiles_directory = home.path().join("dotfiles");
+
+    std::fs::create_dir(&dotfiles_directory)?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["register", dotfiles_directory.to_str().unwrap()]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["rm", "dotfiles"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("p unregister dotfiles"));
+
+    assert!(dotfiles_directory.exists());
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["rm", "--delete-files", "dotfiles"]);
+    cmd.assert().success();
+
+    assert!(!dotfiles_directory.exists());
+
+    // Registered projects inside the projects directory, like nested clones, are removed as usual
+    let nested_directory = home
+        .path()
+        .join("Projects")
+        .join("github.com")
+        .join("owner")
+        .join("repo");
+
+    std::fs::create_dir_all(&nested_directory)?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["register", nested_directory.to_str().unwrap()]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["rm", "repo"]);
+    cmd.assert().success();
+
+    assert!(!nested_directory.exists());
+
+    Ok(())
+}
+
+#[test]
+fn mv_registered_project_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let dotfiles_directory = home.path().join("dotfiles");
+
+    std::fs::create_dir(&dotfiles_directory)?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args([
+        "register",
+        dotfiles_directory.to_str().unwrap(),
+        "--name",
+        "dots",
+    ]);
+    cmd.assert().success();
+
+    // Only the name changes
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["mv", "dots", "dots2"]);
+    cmd.assert().success();
+
+    assert!(dotfiles_directory.exists());
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["go", "dots2"]);
+    cmd.assert().success().stdout(format!(
+        "{}\n",
+        std::fs::canonicalize(&dotfiles_directory)?.display()
+    ));
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.arg("undo");
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["go", "dots"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["mv", "dots", "dots2", "--move-files"]);
+    cmd.assert().success();
+
+    assert!(!dotfiles_directory.exists());
+    assert!(home.path().join("dots2").exists());
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["mv", "dots2", "a/b"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("a/b is not a valid project name"));
+
+    Ok(())
+}
+
+#[test]
+fn mv_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["tag", "add", "demo", "oncall"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["mv", "demo", "showcase"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["tag", "list", "showcase"]);
+    cmd.assert().success().stdout("oncall\n");
+
+    Ok(())
+}
+
+#[test]
+fn clean_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let version_path = home.path().join(".p").join("versions").join("rust.toml");
+    let artifact_directory = home.path().join("Projects").join("demo").join("target");
+    let version_config = std::fs::read_to_string(&version_path)?;
+
+    std::fs::write(
+        &version_path,
+        format!("artifact_dirs = [\"target\"]\n{}", version_config),
+    )?;
+    std::fs::create_dir_all(&artifact_directory)?;
+    std::fs::write(artifact_directory.join("binary"), "0".repeat(2048))?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.arg("clean");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("demo skipped"));
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["clean", "--force"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Reclaimed: 2.0 KB"));
+
+    assert!(!artifact_directory.exists());
+
+    Ok(())
+}
+
+#[test]
+fn stats_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let project_directory = home.path().join("Projects").join("demo");
+
+    std::fs::write(
+        project_directory.join("src").join("main.rs"),
+        "fn main() {\n\n    println!(\"demo\");\n}\n",
+    )?;
+    std::fs::write(project_directory.join(".gitignore"), "generated.rs\n")?;
+    std::fs::write(
+        project_directory.join("generated.rs"),
+        "fn generated() {}\n",
+    )?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["stats", "demo"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Rust: 3 lines in 1 file"));
+
+    Ok(())
+}
+
+#[test]
+fn grep_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let project_directory = home.path().join("Projects").join("demo");
+
+    std::fs::write(
+        project_directory.join("src").join("main.rs"),
+        "fn main() {\n    legacy_api();\n}\n",
+    )?;
+    std::fs::create_dir_all(project_directory.join("target"))?;
+    std::fs::write(project_directory.join(".gitignore"), "target\n")?;
+    std::fs::write(
+        project_directory.join("target").join("out.rs"),
+        "legacy_api();\n",
+    )?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["grep", "legacy_\\w+"]);
+    cmd.assert()
+        .success()
+        .stdout("demo\nsrc/main.rs:2:    legacy_api();\n");
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["grep", "modern_api"]);
+    cmd.assert().failure();
+
+    Ok(())
+}
+
+#[test]
+fn invalid_project_config_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let projects_directory = home.path().join("Projects");
+
+    std::fs::write(
+        projects_directory.join("demo").join("README.md"),
+        "Creates invoices\n",
+    )?;
+    std::fs::create_dir_all(projects_directory.join("broken"))?;
+    std::fs::write(
+        projects_directory.join("broken").join(".p.toml"),
+        "version = 3\n",
+    )?;
+
+    // One broken project is reported without breaking the commands going through all projects
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.arg("list");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("demo (Rust)"))
+        .stderr(predicate::str::contains("Skipping"));
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.arg("stats");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("demo"));
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["grep", "invoices"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("README.md:1:Creates invoices"));
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "--content", "invoices", "-c"]);
+    cmd.assert().success().stdout("demo\n");
+
+    Ok(())
+}
+
+#[test]
+fn find_content_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let projects_directory = home.path().join("Projects");
+
+    std::fs::write(
+        projects_directory.join("demo").join("README.md"),
+        "# Demo\n\nCreates invoices for customers.\n",
+    )?;
+    std::fs::create_dir_all(projects_directory.join("shop"))?;
+    std::fs::write(
+        projects_directory.join("shop").join("package.json"),
+        "{\"name\": \"shop\", \"description\": \"Online shop\"}",
+    )?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "--content", "invoice"]);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "demo\n  README.md: Creates invoices",
+    ));
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "--content", "shop", "-c"]);
+    cmd.assert().success().stdout("shop\n");
+
+    // The index picks up changed files
+    std::fs::write(
+        projects_directory.join("shop").join("package.json"),
+        "{\"name\": \"shop\", \"description\": \"Online shop with invoices\"}",
+    )?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "--content", "invoice", "-c"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("shop\n"));
+
+    Ok(())
+}
+
+#[test]
+fn find_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let projects_directory = home.path().join("Projects");
+
+    std::fs::create_dir_all(projects_directory.join("customer-portal-frontend"))?;
+    std::fs::create_dir_all(projects_directory.join("cli-platform"))?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "cpf", "-c"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("customer-portal-frontend\n"));
+
+    // Without -c the matches are listed under a heading, highlighted unless NO_COLOR is set
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "cpf"]);
+    cmd.assert().success().stdout(predicate::str::starts_with(
+        "Search results for \"cpf\":\ncustomer-portal-frontend\n",
+    ));
+
+    // Falls back to typo tolerant matching
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "cutsomer", "-c"]);
+    cmd.assert().success().stdout("customer-portal-frontend\n");
+
+    std::fs::create_dir_all(projects_directory.join("CPF-tools"))?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "CPF", "-c", "--case", "sensitive"]);
+    cmd.assert().success().stdout("CPF-tools\n");
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "CPF", "-c", "--case", "insensitive"]);
+    cmd.assert().success().stdout(
+        predicate::str::contains("CPF-tools\n")
+            .and(predicate::str::contains("customer-portal-frontend\n")),
+    );
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "Projects/dem", "-c", "--match-on", "path"]);
+    cmd.assert().success().stdout("demo\n");
+
+    Ok(())
+}
+
+#[test]
+fn resolve_project_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let projects_directory = home.path().join("Projects");
+
+    std::fs::create_dir_all(projects_directory.join("customer-portal"))?;
+    std::fs::create_dir_all(projects_directory.join("customer-api"))?;
+
+    // A unique prefix is accepted
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["go", "de"]);
+    cmd.assert()
+        .success()
+        .stdout(format!("{}\n", projects_directory.join("demo").display()));
+
+    // Ambiguous names list the matches instead of guessing
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["go", "customer"]);
+    cmd.assert().failure().stdout("").stderr(
+        predicate::str::contains("customer-api").and(predicate::str::contains("customer-portal")),
+    );
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["tag", "add", "cportal", "work"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["tag", "list", "customer-portal"]);
+    cmd.assert().success().stdout("work\n");
+
+    Ok(())
+}
+
+#[test]
+fn info_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let project_directory = home.path().join("Projects").join("demo");
+
+    std::fs::write(
+        project_directory.join("README.md"),
+        "# Demo\n\n[![CI](https://example.com/badge.svg)](https://example.com)\n\nCreates invoices\nfor customers.\n\n## Usage\n",
+    )?;
+
+    // The name comes from the project, not from the current directory
+    let mut cmd = p_in(&home, "Projects/demo/src")?;
+
+    cmd.args(["info", "--only", "root,readme"]);
+    cmd.assert().success().stdout(format!(
+        "Project: demo\nRoot: {}\nREADME:\nCreates invoices for customers.\n",
+        project_directory.display()
+    ));
+
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["info"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Version: Rust"))
+        .stdout(predicate::str::contains("Size: ").not())
+        .stdout(predicate::str::contains("Last modified: ").not());
+
+    let mut cmd = p_in(&home, "Projects/demo/src")?;
+
+    cmd.args(["info", "--full", "--hide", "readme"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Version: Rust"))
+        .stdout(predicate::str::contains("Size: "))
+        .stdout(predicate::str::contains("README:").not());
+
+    Ok(())
+}
+
+#[test]
+fn info_by_name_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let projects_directory = home.path().join("Projects");
+
+    std::fs::create_dir_all(projects_directory.join("website"))?;
+
+    let mut cmd = p_in(&home, ".")?;
+
+    cmd.args(["info", "webs", "--only", "root"]);
+    cmd.assert().success().stdout(format!(
+        "Project: website\nRoot: {}\n",
+        projects_directory.join("website").display()
+    ));
+
+    let mut cmd = p_in(&home, ".")?;
+
+    cmd.args(["info", "--all", "--only", "tags"]);
+    cmd.assert()
+        .success()
+        .stdout("Project: demo\n\nProject: website\n");
+
+    // A project with an invalid config is reported without hiding the others
+    std::fs::create_dir_all(projects_directory.join("broken"))?;
+    std::fs::write(
+        projects_directory.join("broken").join(".p.toml"),
+        "version = \"Nope\"\n",
+    )?;
+
+    let mut cmd = p_in(&home, ".")?;
+
+    cmd.args(["info", "--all", "--only", "tags"]);
+    cmd.assert()
+        .failure()
+        .stdout("Project: broken\n\nProject: demo\n\nProject: website\n")
+        .stderr(predicate::str::contains("Unknown version Nope in .p.toml"));
+
+    Ok(())
+}
+
+#[test]
+fn frecency_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let projects_directory = home.path().join("Projects");
+
+    std::fs::create_dir_all(projects_directory.join("api-one"))?;
+    std::fs::create_dir_all(projects_directory.join("api-two"))?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "api", "-c"]);
+    cmd.assert().success().stdout("api-one\napi-two\n");
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["go", "api-two"]);
+    cmd.assert().success();
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "api", "-c"]);
+    cmd.assert().success().stdout("api-two\napi-one\n");
+
+    // A recent visit outweighs many old ones
+    let current_timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+
+    std::fs::write(
+        home.path().join(".p").join("visits.json"),
+        format!(
+            "{{\"api-one\": {{\"count\": 1, \"last_visit\": {}}}, \"api-two\": {{\"count\": 10, \"last_visit\": 0}}}}",
+            current_timestamp
+        ),
+    )?;
+
+    let mut cmd = p_in(&home, "Projects")?;
+
+    cmd.args(["find", "api", "-c"]);
+    cmd.assert().success().stdout("api-one\napi-two\n");
+
+    Ok(())
+}
+
+#[test]
+fn editor_precedence_test() -> Result<(), Box<dyn std::error::Error>> {
+    let home = setup_home()?;
+    let config_path = home.path().join(".p").join("config.toml");
+    let version_path = home.path().join(".p").join("versions").join("rust.toml");
+    let project_directory = home.path().join("Projects").join("demo");
+    let base_config = std::fs::read_to_string(&config_path)?;
+    let base_version = std::fs::read_to_string(&version_path)?;
+
+    // Each level is added on top of the previous ones and has to win over them
+    let levels: [(&str, &str); 7] = [
+        ("", "Editor: None\n"),
+        ("EDITOR", "Editor: env-editor\nFrom $EDITOR\n"),
+        ("VISUAL", "Editor: visual-editor\nFrom $VISUAL\n"),
+        ("config", "Editor: config-editor\nFrom config\n"),
+        ("version", "Editor: version-editor\nFrom version Rust\n"),
+        (".p.toml", "Editor: project-config-editor\nFrom .p.toml\n"),
+        (
+            "project_editors",
+            "Editor: project-editor\nFrom project_editors in config\n",
+        ),
+    ];
+    let mut environment: Vec<(&str, &str)> = vec![];
+
+    for (level, expected_output) in levels {
+        match level {
+            "EDITOR" => environment.push(("EDITOR", "env-editor")),
+            "VISUAL" => environment.push(("VISUAL", "visual-editor")),
+            "config" => std::fs::write(
+                &config_path,
+                format!("{}editor = \"config-editor\"\n", base_config),
+            )?,
+            "version" => std::fs::write(
+                &version_path,
+                format!("{}editor = \"version-editor\"\n", base_version),
+            )?,
+            ".p.toml" => std::fs::write(
+                project_directory.join(".p.toml"),
+                "editor = \"project-config-editor\"\n",
+            )?,
+            "project_editors" => std::fs::write(
+                &config_path,
+                format!(
+                    "{}editor = \"config-editor\"\n\n[project_editors]\ndemo = \"project-editor\"\n",
+                    base_config
+                ),
+            )?,
+            _ => {}
+        }
+
+        let mut cmd = p_in(&home, "Projects/demo")?;
+
+        cmd.env_remove("VISUAL").env_remove("EDITOR");
+        cmd.envs(environment.iter().cloned());
+        cmd.args(["info", "--only", "editor"]);
+        cmd.assert()
+            .success()
+            .stdout(format!("Project: demo\n{}", expected_output));
+    }
+
+    // The --editor flag wins over everything
+    let mut cmd = p_in(&home, "Projects/demo")?;
+
+    cmd.args(["edit", "--editor", "echo flag"]);
+    cmd.assert()
+        .success()
+        .stdout(format!("flag {}\n", project_directory.display()));
+
+    Ok(())
+}